@@ -0,0 +1,71 @@
+extern crate rust_examples;
+use rust_examples::adts::*;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Tree sizes must be odd since a [Tree] is a *full* binary tree (sizes of the form `2^h - 1`
+/// additionally make the balanced trees perfect)
+const SIZES: [u64; 2] = [127, 1023];
+
+/// Builds a degenerate (linked-list-shaped) BST (of depth `(n - 1) / 2`) from sorted `items` of
+/// odd size `n`, where each inner node has a leaf as its left child.
+fn degenerate(items: &[(u64, u64)]) -> Tree<'_, u64, u64> {
+    match items {
+        [(key, data)] => Tree::Leaf(*key, data),
+        [(lkey, ldata), (key, data), rest @ ..] => Tree::Node {
+            key: *key,
+            data,
+            left: Box::new(Tree::Leaf(*lkey, ldata)),
+            right: Box::new(degenerate(rest)),
+        },
+        [] => unreachable!("tree size must be odd"),
+    }
+}
+
+fn bench_lookup(c: &mut Criterion) {
+    // Define a benchmark group for key lookups in trees of different shapes
+    let mut group = c.benchmark_group("Tree Lookup");
+
+    for size in SIZES {
+        let items = (0..size).map(|k| (k, k * k)).collect::<Vec<_>>();
+        let keys = items.iter().map(|(k, _)| *k).collect::<Vec<_>>();
+
+        let trees = [
+            ("Balanced", Tree::from_sorted(&items).expect("sorted keys")),
+            ("Degenerate", degenerate(&items)),
+        ];
+
+        for (shape, tree) in trees.iter() {
+            // Benchmark linear traversal which ignores the ordering of keys
+            group.bench_with_input(
+                BenchmarkId::new(format!("Linear search - {}", shape), size),
+                &keys,
+                |b, keys| {
+                    b.iter(|| {
+                        keys.iter()
+                            .filter_map(|k| tree.search(black_box(k)))
+                            .sum::<u64>()
+                    });
+                },
+            );
+
+            // Benchmark lookup which follows the BST ordering and thus depends on the depth
+            group.bench_with_input(
+                BenchmarkId::new(format!("BST search - {}", shape), size),
+                &keys,
+                |b, keys| {
+                    b.iter(|| {
+                        keys.iter()
+                            .filter_map(|k| tree.search_bst(black_box(k)))
+                            .sum::<u64>()
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_lookup);
+criterion_main!(benches);
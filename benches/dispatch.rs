@@ -22,5 +22,89 @@ fn bench_quadratic(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_quadratic);
+fn bench_collection(c: &mut Criterion) {
+    // Define a benchmark group for Gradient Descent over a heterogeneous collection of functions
+    let mut group = c.benchmark_group("GD - Collection");
+
+    // Benchmark GD with dynamic dispatch on trait objects
+    group.bench_function("Dynamic Dispatch", |b| {
+        let functions: Vec<Box<dyn Differentiable>> = vec![
+            Quadratic::heap_alloc(2., 1., 0.),
+            Box::new(Trigonometric::Sine),
+            Box::new(Trigonometric::Cosine),
+        ];
+        b.iter(|| {
+            functions
+                .iter()
+                .map(|f| gradient_descent_dynamic(f.as_ref(), 10_000, 0.01))
+                .sum::<f64>()
+        });
+    });
+
+    // Benchmark GD with static dispatch on an enum
+    group.bench_function("Enum Dispatch", |b| {
+        let functions: Vec<AnyDifferentiable> = vec![
+            Quadratic::stack_alloc(2., 1., 0.).into(),
+            Trigonometric::Sine.into(),
+            Trigonometric::Cosine.into(),
+        ];
+        b.iter(|| {
+            functions
+                .iter()
+                .map(|f| gradient_descent_static(f, 10_000, 0.01))
+                .sum::<f64>()
+        });
+    });
+
+    group.finish();
+}
+
+/// Finds the smallest number of iterations after which `strategy` minimizes `f` within `eps` of
+/// the known minimum `x_min`
+fn iters_to_converge(f: &Quadratic, strategy: Strategy, x_min: f64, eps: f64) -> usize {
+    (1..=100_000)
+        .find(|&n| (minimize(f, strategy, n) - x_min).abs() < eps)
+        .expect("strategy should converge")
+}
+
+fn bench_optimizers(c: &mut Criterion) {
+    // Define a benchmark group for different optimizers on a quadratic function
+    let mut group = c.benchmark_group("Optimizers - Quadratic");
+
+    // min { 2*x^2 - x } = -1/8 at x = 1/4
+    let function = Quadratic::stack_alloc(2., 1., 0.);
+
+    let strategies = [
+        ("Vanilla", Strategy::Vanilla { eta: 0.01 }),
+        (
+            "Momentum",
+            Strategy::Momentum {
+                eta: 0.01,
+                beta: 0.9,
+            },
+        ),
+        (
+            "Adam",
+            Strategy::Adam {
+                eta: 0.01,
+                beta1: 0.9,
+                beta2: 0.999,
+                eps: 1e-8,
+            },
+        ),
+    ];
+
+    // Benchmark each optimizer with the number of iterations it needs to converge, so that both
+    // the iteration count (in the name) and the total time to convergence can be compared
+    for (name, strategy) in strategies {
+        let iters = iters_to_converge(&function, strategy, 0.25, 1e-5);
+        group.bench_function(format!("{} ({} iterations)", name, iters), |b| {
+            b.iter(|| minimize(&function, strategy, iters));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_quadratic, bench_collection, bench_optimizers);
 criterion_main!(benches);
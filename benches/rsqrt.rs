@@ -0,0 +1,60 @@
+extern crate rust_examples;
+use rust_examples::rsqrt::*;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Fixed set of inputs spanning several orders of magnitude
+const INPUTS: [f32; 8] = [1e-6, 0.015625, 0.5, 1.0, 2.0, 42.0, 1234.5678, 1e12];
+
+fn bench_rsqrt(c: &mut Criterion) {
+    // Define a benchmark group for the inverse square root
+    let mut group = c.benchmark_group("Inverse Square Root");
+
+    let inputs = INPUTS
+        .iter()
+        .map(|&x| PositiveFloat::new(x).expect("positive normal float"))
+        .collect::<Vec<_>>();
+
+    // Benchmark fast inverse square root with 1, 2 and 3 iterations of the Newton's method
+    group.bench_function("Fast (1 iteration)", |b| {
+        b.iter(|| {
+            black_box(&inputs)
+                .iter()
+                .map(|x| x.fast_rsqrt::<1>().inner())
+                .sum::<f32>()
+        });
+    });
+
+    group.bench_function("Fast (2 iterations)", |b| {
+        b.iter(|| {
+            black_box(&inputs)
+                .iter()
+                .map(|x| x.fast_rsqrt::<2>().inner())
+                .sum::<f32>()
+        });
+    });
+
+    group.bench_function("Fast (3 iterations)", |b| {
+        b.iter(|| {
+            black_box(&inputs)
+                .iter()
+                .map(|x| x.fast_rsqrt::<3>().inner())
+                .sum::<f32>()
+        });
+    });
+
+    // Benchmark exact inverse square root via std
+    group.bench_function("Std (sqrt + recip)", |b| {
+        b.iter(|| {
+            black_box(&INPUTS)
+                .iter()
+                .map(|x| x.sqrt().recip())
+                .sum::<f32>()
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_rsqrt);
+criterion_main!(benches);
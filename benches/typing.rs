@@ -0,0 +1,81 @@
+extern crate rust_examples;
+use rust_examples::typing::*;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::cmp::Ordering;
+
+/// Number of compared floats
+const SIZE: usize = 10_000;
+
+/// Generates `SIZE` pseudo-random positive floats in `[0, 1e6)` by a simple *linear congruential
+/// generator* so that the inputs are deterministic.
+fn inputs() -> Vec<f64> {
+    let mut state = 42u64;
+    (0..SIZE)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 1e6
+        })
+        .collect()
+}
+
+/// Counts adjacent pairs `(a, b)` of `values` such that `a < b` according to given `cmp`
+fn count_less<T: Copy>(values: &[T], cmp: impl Fn(T, T) -> Ordering) -> usize {
+    values
+        .windows(2)
+        .filter(|w| cmp(w[0], w[1]) == Ordering::Less)
+        .count()
+}
+
+fn bench_cmp(c: &mut Criterion) {
+    // Define a benchmark group for the comparison of positive floats
+    let mut group = c.benchmark_group("Positive Float Comparison");
+
+    let values = inputs();
+
+    // Note: The inputs are wrapped into `Positive` *outside* of the timed loop. This shifts the
+    // cost of the validation from each comparison to the construction of the values, which is
+    // paid once (and typically at the boundary of the system) rather than on each call. The last
+    // benchmark includes the wrapping to show the total cost.
+    let positives = values
+        .iter()
+        .map(|&x| Positive::new(x).expect("positive number"))
+        .collect::<Vec<_>>();
+
+    // Benchmark the naive comparison which panics on invalid inputs
+    group.bench_function("Naive (panic)", |b| {
+        b.iter(|| count_less(black_box(&values), cmp_f64));
+    });
+
+    // Benchmark the comparison which checks the inputs and returns an `Option`
+    group.bench_function("Better (Option)", |b| {
+        b.iter(|| {
+            count_less(black_box(&values), |x, y| {
+                better_cmp_f64(x, y).expect("positive numbers")
+            })
+        });
+    });
+
+    // Benchmark the comparison of pre-wrapped `Positive` numbers
+    group.bench_function("Safe (Positive)", |b| {
+        b.iter(|| count_less(black_box(&positives), safe_cmp_f64));
+    });
+
+    // Benchmark the comparison of `Positive` numbers including the wrapping
+    group.bench_function("Safe (Positive) incl. wrapping", |b| {
+        b.iter(|| {
+            let positives = black_box(&values)
+                .iter()
+                .map(|&x| Positive::new(x).expect("positive number"))
+                .collect::<Vec<_>>();
+            count_less(&positives, safe_cmp_f64)
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_cmp);
+criterion_main!(benches);
@@ -2,6 +2,7 @@
 //! [Algebraic Data Type (ADT)](https://en.wikipedia.org/wiki/Algebraic_data_type) and the
 //! concept of [pattern matching](https://en.wikipedia.org/wiki/Pattern_matching) which is commonly
 //! used to work with ADTs.
+use std::fmt::{self, Write};
 
 /// An enum representing an Binary Tree Algebraic Data Type (ADT)
 ///
@@ -21,7 +22,11 @@
 /// ```haskell
 /// data Tree k v = Leaf k v | Node { key :: k, data :: v, left :: (Tree k v), right :: (Tree k v) }
 /// ```
-#[derive(Debug)]
+///
+/// Two trees are equal if they have the same structure, keys and data. Note that the data are
+/// compared by value, not by the references, so trees pointing to different (but equal) data are
+/// equal as well.
+#[derive(Debug, PartialEq)]
 pub enum Tree<'a, K, V> {
     Leaf(K, &'a V),
     Node {
@@ -82,17 +87,596 @@ impl<'a, K: PartialEq + Eq, V> Tree<'a, K, V> {
             }
         }
     }
+
+    /// Bounded version of [Tree::search] which only descends at most `max_depth` levels below
+    /// the root (i.e. the root is at depth 0 and `max_depth = 0` only checks the root).
+    ///
+    /// Returns `None` if the key is not present or if it's deeper than `max_depth`, so the cost of
+    /// a lookup is bounded even for arbitrarily deep trees.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::adts::Tree;
+    ///
+    /// let tree = Tree::Node {
+    ///     key: 2,
+    ///     data: &"two",
+    ///     left: Box::new(Tree::Leaf(1, &"one")),
+    ///     right: Box::new(Tree::Leaf(3, &"three")),
+    /// };
+    ///
+    /// assert_eq!(tree.search_depth(&3, 0), None);
+    /// assert_eq!(tree.search_depth(&3, 1), Some(&"three"));
+    /// ```
+    pub fn search_depth(&self, lookup_key: &K, max_depth: usize) -> Option<&'a V> {
+        match self {
+            Self::Leaf(key, data) | Self::Node { key, data, .. } if key == lookup_key => {
+                Some(*data)
+            }
+            Self::Node { left, right, .. } if max_depth > 0 => left
+                .search_depth(lookup_key, max_depth - 1)
+                .or_else(|| right.search_depth(lookup_key, max_depth - 1)),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Tree<'a, K, V> {
+    /// Builds a balanced BST (see [Tree::search_bst]) from `keys_and_data` sorted by the keys in
+    /// strictly ascending order or returns `None` if the keys are not sorted.
+    ///
+    /// The tree is built by recursively choosing the midpoint of the slice as the root, so the
+    /// [depth](Tree::depth) of the result is logarithmic in the number of elements. The data are
+    /// borrowed from the slice, only the keys are cloned.
+    ///
+    /// Note that every [Tree::Node] has exactly two children (there's no empty tree), so a [Tree]
+    /// always has an odd number of elements. Therefore `None` is returned as well if the slice is
+    /// empty or of even length.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::adts::Tree;
+    ///
+    /// let items = [(1, "one"), (2, "two"), (3, "three"), (4, "four"), (5, "five")];
+    ///
+    /// let tree = Tree::from_sorted(&items).expect("sorted keys");
+    /// assert_eq!(tree.search_bst(&4), Some(&"four"));
+    /// assert_eq!(tree.depth(), 2);
+    ///
+    /// assert!(Tree::from_sorted(&[(2, "two"), (1, "one"), (3, "three")]).is_none());
+    /// ```
+    pub fn from_sorted(keys_and_data: &'a [(K, V)]) -> Option<Self>
+    where
+        K: Clone,
+    {
+        let sorted = keys_and_data.windows(2).all(|w| w[0].0 < w[1].0);
+        if sorted && keys_and_data.len() % 2 == 1 {
+            Some(Self::build_balanced(keys_and_data))
+        } else {
+            None
+        }
+    }
+
+    /// Builds a balanced tree from a non-empty slice of odd length
+    fn build_balanced(items: &'a [(K, V)]) -> Self
+    where
+        K: Clone,
+    {
+        // Both sub-trees must have an odd number of elements as well, so the midpoint is adjusted
+        // to the nearest one which splits the rest into two odd halves
+        let half = items.len() / 2;
+        let mid = if half % 2 == 1 {
+            half
+        } else {
+            half.saturating_sub(1)
+        };
+
+        let (key, data) = &items[mid];
+        if items.len() == 1 {
+            Self::Leaf(key.clone(), data)
+        } else {
+            Self::Node {
+                key: key.clone(),
+                data,
+                left: Box::new(Self::build_balanced(&items[..mid])),
+                right: Box::new(Self::build_balanced(&items[mid + 1..])),
+            }
+        }
+    }
+
+    /// Lookup method which assumes that this tree is a *Binary Search Tree (BST)*, i.e. that all
+    /// the keys in the left sub-tree of a node are less than the node's key and all the keys in
+    /// the right sub-tree are greater.
+    ///
+    /// Contrary to [Tree::search] which might have to visit all the nodes, this descends into at
+    /// most one sub-tree on each level, so the cost of a lookup is proportional to the *depth* of
+    /// the tree. The depth of a balanced tree is logarithmic in its size while for a degenerate
+    /// (linked-list-shaped) tree it's linear.
+    ///
+    /// Note that the result is meaningless if the tree does not satisfy the BST ordering.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::adts::Tree;
+    ///
+    /// let tree = Tree::Node {
+    ///     key: 2,
+    ///     data: &"two",
+    ///     left: Box::new(Tree::Leaf(1, &"one")),
+    ///     right: Box::new(Tree::Leaf(3, &"three")),
+    /// };
+    ///
+    /// assert_eq!(tree.search_bst(&3), Some(&"three"));
+    /// assert_eq!(tree.search_bst(&4), None);
+    /// ```
+    pub fn search_bst(&self, lookup_key: &K) -> Option<&'a V> {
+        let mut tree = self;
+        // Only one path is followed, so the recursion can be replaced by a simple loop
+        loop {
+            match tree {
+                Self::Leaf(key, data) => return if key == lookup_key { Some(*data) } else { None },
+                Self::Node {
+                    key,
+                    data,
+                    left,
+                    right,
+                } => match lookup_key.cmp(key) {
+                    std::cmp::Ordering::Less => tree = left,
+                    std::cmp::Ordering::Greater => tree = right,
+                    std::cmp::Ordering::Equal => return Some(*data),
+                },
+            }
+        }
+    }
+}
+
+/// Visitor which defines an operation over a [Tree] separately from the ADT itself.
+///
+/// The [visitor pattern](https://en.wikipedia.org/wiki/Visitor_pattern) is an alternative to
+/// inherent methods on [Tree]. New operations can be added without touching the tree
+/// definition, while [Tree::accept] encapsulates how the structure is traversed.
+pub trait TreeVisitor<K, V> {
+    /// Called for each [Tree::Leaf] with its key and data
+    fn visit_leaf(&mut self, key: &K, data: &V);
+
+    /// Called for each [Tree::Node] with its key and data (before its sub-trees are visited)
+    fn visit_node(&mut self, key: &K, data: &V);
+}
+
+impl<'a, K, V> Tree<'a, K, V> {
+    /// Returns the depth of this tree, i.e. the number of edges on the longest path from the root
+    /// to a leaf (so a single [Tree::Leaf] has depth 0).
+    pub fn depth(&self) -> usize {
+        self.cata(|_, _| 0, |_, _, l, r| 1 + l.max(r))
+    }
+
+    /// Drives the traversal of this tree with given `visitor` in *pre-order* (i.e. an inner node
+    /// is visited before its left and then right sub-tree).
+    pub fn accept(&self, visitor: &mut impl TreeVisitor<K, V>) {
+        match self {
+            Self::Leaf(key, data) => visitor.visit_leaf(key, data),
+            Self::Node {
+                key,
+                data,
+                left,
+                right,
+            } => {
+                visitor.visit_node(key, data);
+                left.accept(visitor);
+                right.accept(visitor);
+            }
+        }
+    }
+
+    /// Generic fold (a.k.a. *catamorphism*) which collapses this tree bottom-up into a single
+    /// value of type `R`.
+    ///
+    /// Each [Tree::Leaf] is turned into an `R` by `leaf` and each [Tree::Node] combines its own
+    /// key and data with the results `R` of its left and right sub-tree using `node`. This is the
+    /// principled way of expressing any aggregation over the ADT - e.g. its height, number of
+    /// elements or even a search.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::adts::Tree;
+    ///
+    /// let tree = Tree::Node {
+    ///     key: 2,
+    ///     data: &"two",
+    ///     left: Box::new(Tree::Leaf(1, &"one")),
+    ///     right: Box::new(Tree::Leaf(3, &"three")),
+    /// };
+    ///
+    /// let len = tree.cata(|_, _| 1, |_, _, l, r| 1 + l + r);
+    /// assert_eq!(len, 3);
+    /// ```
+    pub fn cata<R, L, N>(&self, leaf: L, node: N) -> R
+    where
+        L: Fn(&K, &V) -> R,
+        N: Fn(&K, &V, R, R) -> R,
+    {
+        // Note: Recursing directly on `cata` with `&leaf` and `&node` would require an infinite
+        // number of monomorphized instances (`L`, `&L`, `&&L`, ...), hence the helper.
+        self.cata_ref(&leaf, &node)
+    }
+
+    /// Folds this tree *in-order* (left sub-tree, node, right sub-tree) into a single value by
+    /// threading the accumulator, starting with `init`, through `f` applied to each node.
+    ///
+    /// Contrary to [Tree::cata], which collapses the tree bottom-up following its structure, this
+    /// is a *left fold* which sees the tree as a sequence of `(key, data)` pairs.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::adts::Tree;
+    ///
+    /// let tree = Tree::Node {
+    ///     key: 2,
+    ///     data: &"b",
+    ///     left: Box::new(Tree::Leaf(1, &"a")),
+    ///     right: Box::new(Tree::Leaf(3, &"c")),
+    /// };
+    ///
+    /// let keys = tree.fold(Vec::new(), |mut keys, k, _| {
+    ///     keys.push(*k);
+    ///     keys
+    /// });
+    /// assert_eq!(keys, vec![1, 2, 3]);
+    /// ```
+    pub fn fold<B, F>(&self, init: B, f: F) -> B
+    where
+        F: Fn(B, &K, &V) -> B,
+    {
+        self.fold_ref(init, &f)
+    }
+
+    fn fold_ref<B, F>(&self, acc: B, f: &F) -> B
+    where
+        F: Fn(B, &K, &V) -> B,
+    {
+        match self {
+            Self::Leaf(key, data) => f(acc, key, data),
+            Self::Node {
+                key,
+                data,
+                left,
+                right,
+            } => {
+                let acc = left.fold_ref(acc, f);
+                let acc = f(acc, key, data);
+                right.fold_ref(acc, f)
+            }
+        }
+    }
+
+    /// Applies `f` to the data of every node while keeping the keys and the shape of this tree.
+    ///
+    /// This makes [Tree] a *functor* (in its values). Because this tree only references its data,
+    /// the transformed values must be owned by someone, so the result is an [OwnedTree].
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::adts::{OwnedTree, Tree};
+    ///
+    /// let tree = Tree::Node {
+    ///     key: 2,
+    ///     data: &20,
+    ///     left: Box::new(Tree::Leaf(1, &10)),
+    ///     right: Box::new(Tree::Leaf(3, &30)),
+    /// };
+    ///
+    /// let mapped = tree.map(|v| v.to_string());
+    /// assert_eq!(mapped.search(&3), Some(&"30".to_string()));
+    /// ```
+    pub fn map<W, F: Fn(&V) -> W>(&self, f: F) -> OwnedTree<K, W>
+    where
+        K: Clone,
+    {
+        self.cata(
+            |key, data| OwnedTree::Leaf(key.clone(), f(data)),
+            |key, data, left, right| OwnedTree::Node {
+                key: key.clone(),
+                data: f(data),
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        )
+    }
+
+    /// Renders this tree as a [Graphviz](https://graphviz.org) DOT digraph which can be visualized
+    /// e.g. by `dot -Tsvg`.
+    ///
+    /// Each node (both [Tree::Leaf] and [Tree::Node]) gets a unique id (in pre-order) and is
+    /// labeled by its key, and there's an edge from each inner node to both of its children.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::adts::Tree;
+    ///
+    /// let tree = Tree::Node {
+    ///     key: 2,
+    ///     data: &"two",
+    ///     left: Box::new(Tree::Leaf(1, &"one")),
+    ///     right: Box::new(Tree::Leaf(3, &"three")),
+    /// };
+    ///
+    /// let dot = tree.to_dot();
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.contains("n0 -> n2;"));
+    /// ```
+    pub fn to_dot(&self) -> String
+    where
+        K: fmt::Display,
+    {
+        let mut dot = String::from("digraph {\n");
+        self.write_dot(&mut dot, &mut 0)
+            .expect("writing to a String never fails");
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Writes nodes and edges of this (sub-)tree with ids starting at `next_id` and returns the id
+    /// of the root.
+    fn write_dot(&self, dot: &mut String, next_id: &mut usize) -> Result<usize, fmt::Error>
+    where
+        K: fmt::Display,
+    {
+        let id = *next_id;
+        *next_id += 1;
+
+        match self {
+            Self::Leaf(key, _) => writeln!(dot, "    n{} [label=\"{}\"];", id, key)?,
+            Self::Node {
+                key, left, right, ..
+            } => {
+                writeln!(dot, "    n{} [label=\"{}\"];", id, key)?;
+                let left = left.write_dot(dot, next_id)?;
+                let right = right.write_dot(dot, next_id)?;
+                writeln!(dot, "    n{} -> n{};", id, left)?;
+                writeln!(dot, "    n{} -> n{};", id, right)?;
+            }
+        }
+
+        Ok(id)
+    }
+
+    fn cata_ref<R, L, N>(&self, leaf: &L, node: &N) -> R
+    where
+        L: Fn(&K, &V) -> R,
+        N: Fn(&K, &V, R, R) -> R,
+    {
+        match self {
+            Self::Leaf(key, data) => leaf(key, data),
+            Self::Node {
+                key,
+                data,
+                left,
+                right,
+            } => {
+                let l = left.cata_ref(leaf, node);
+                let r = right.cata_ref(leaf, node);
+                node(key, data, l, r)
+            }
+        }
+    }
+}
+
+/// Renders the tree *sideways* - each key is on a separate line indented by its depth, with the
+/// right sub-tree above and the left sub-tree below its parent (i.e. the tree is rotated by 90
+/// degrees counter-clockwise).
+///
+/// # Example
+/// ```
+/// use rust_examples::adts::Tree;
+///
+/// let tree = Tree::Node {
+///     key: 2,
+///     data: &"two",
+///     left: Box::new(Tree::Leaf(1, &"one")),
+///     right: Box::new(Tree::Leaf(3, &"three")),
+/// };
+///
+/// assert_eq!(tree.to_string(), "    3\n2\n    1\n");
+/// ```
+impl<'a, K: fmt::Display, V> fmt::Display for Tree<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_sideways(f, 0)
+    }
+}
+
+impl<'a, K: fmt::Display, V> Tree<'a, K, V> {
+    const INDENT: &'static str = "    ";
+
+    fn fmt_sideways(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        match self {
+            Self::Leaf(key, _) => writeln!(f, "{}{}", Self::INDENT.repeat(depth), key),
+            Self::Node {
+                key, left, right, ..
+            } => {
+                right.fmt_sideways(f, depth + 1)?;
+                writeln!(f, "{}{}", Self::INDENT.repeat(depth), key)?;
+                left.fmt_sideways(f, depth + 1)
+            }
+        }
+    }
+}
+
+/// [TreeVisitor] which counts the leaves and inner nodes of a [Tree]
+#[derive(Debug, Default)]
+pub struct CountingVisitor {
+    pub leaves: usize,
+    pub nodes: usize,
+}
+
+impl<K, V> TreeVisitor<K, V> for CountingVisitor {
+    fn visit_leaf(&mut self, _key: &K, _data: &V) {
+        self.leaves += 1;
+    }
+
+    fn visit_node(&mut self, _key: &K, _data: &V) {
+        self.nodes += 1;
+    }
+}
+
+/// [TreeVisitor] which collects all the keys of a [Tree] in the order they were visited
+#[derive(Debug, Default)]
+pub struct KeyCollectingVisitor<K> {
+    pub keys: Vec<K>,
+}
+
+impl<K: Clone, V> TreeVisitor<K, V> for KeyCollectingVisitor<K> {
+    fn visit_leaf(&mut self, key: &K, _data: &V) {
+        self.keys.push(key.clone());
+    }
+
+    fn visit_node(&mut self, key: &K, _data: &V) {
+        self.keys.push(key.clone());
+    }
+}
+
+/// Binary tree ADT which, contrary to [Tree], *owns* the data stored in its nodes.
+///
+/// Owning the data means that the tree is not bound by any lifetime and its values can be
+/// modified in place (see [OwnedTree::update]).
+#[derive(Clone, Debug, PartialEq)]
+pub enum OwnedTree<K, V> {
+    Leaf(K, V),
+    Node {
+        key: K,
+        data: V,
+        left: Box<Self>,
+        right: Box<Self>,
+    },
+}
+
+impl<K, V> OwnedTree<K, V> {
+    /// Iterates over `(key, data)` pairs of this tree *in-order* (i.e. the left sub-tree of an
+    /// inner node is visited before the node itself and the right sub-tree after it).
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let mut items = Vec::new();
+        self.collect_in_order(&mut items);
+        items.into_iter()
+    }
+
+    fn collect_in_order<'t>(&'t self, items: &mut Vec<(&'t K, &'t V)>) {
+        match self {
+            Self::Leaf(key, data) => items.push((key, data)),
+            Self::Node {
+                key,
+                data,
+                left,
+                right,
+            } => {
+                left.collect_in_order(items);
+                items.push((key, data));
+                right.collect_in_order(items);
+            }
+        }
+    }
 }
 
+impl<K: PartialEq + Eq, V> OwnedTree<K, V> {
+    /// Lookup method analogous to [Tree::search] which returns a reference to the data stored
+    /// under `lookup_key` if it's present in this tree.
+    pub fn search(&self, lookup_key: &K) -> Option<&V> {
+        match self {
+            Self::Leaf(key, data) | Self::Node { key, data, .. } if key == lookup_key => Some(data),
+            Self::Leaf(_, _) => None,
+            Self::Node { left, right, .. } => {
+                left.search(lookup_key).or_else(|| right.search(lookup_key))
+            }
+        }
+    }
+
+    /// Applies `f` to the data stored under `lookup_key` in place and returns `true` if the key
+    /// was found. Otherwise the tree is left unchanged and `false` is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::adts::OwnedTree;
+    ///
+    /// let mut tree = OwnedTree::Node {
+    ///     key: 2,
+    ///     data: 20,
+    ///     left: Box::new(OwnedTree::Leaf(1, 10)),
+    ///     right: Box::new(OwnedTree::Leaf(3, 30)),
+    /// };
+    ///
+    /// assert!(tree.update(&3, |v| *v += 1));
+    /// assert_eq!(tree.search(&3), Some(&31));
+    /// ```
+    pub fn update<F: FnOnce(&mut V)>(&mut self, lookup_key: &K, f: F) -> bool {
+        self.search_mut(lookup_key).map(f).is_some()
+    }
+
+    fn search_mut(&mut self, lookup_key: &K) -> Option<&mut V> {
+        match self {
+            Self::Leaf(key, data) | Self::Node { key, data, .. } if key == lookup_key => Some(data),
+            Self::Leaf(_, _) => None,
+            // Note: The left and right sub-trees are disjoint, so both can be borrowed mutably
+            Self::Node { left, right, .. } => left
+                .search_mut(lookup_key)
+                .or_else(|| right.search_mut(lookup_key)),
+        }
+    }
+}
+
+/// This test demonstrates that in Rust all *self-referential* structures must have size known at
+/// compile time. This means that such structures *cannot own* data of type `Self` but rather have
+/// to indirectly refence these via some sort of a pointer.
+///
+/// # Example
+/// ```compile_fail
+/// enum PList<T> {
+///     Nil,
+///     Cons(T, Self),
+/// }
+/// ```
+///
+/// In order to make the example above compile, one would have to use some sort of indirection for
+/// the `Self` owned by the `Cons` variant. This indirection can be realized by either
+///  * a refence to stack-allocated data (`&`)
+///  * a refence to heap-allocated data ([Box])
+///  * a refence counting pointer ([std::rc::Rc])
+///
+/// or similar pointer-like structure which has *defined size* - i.e. is known not to be
+/// infinite (of unbounded memory).
+pub struct SelfReferentialStructureTest;
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use rstest::*;
+
+    static DATA: [&str; 5] = [
+        "root node",
+        "inner node",
+        "1st leaf",
+        "2nd leaf",
+        "3rd leaf",
+    ];
+
+    /// The same tree as in [binary_tree] but referencing static data.
+    #[fixture]
+    fn tree() -> Tree<'static, i32, &'static str> {
+        Tree::Node {
+            key: 42,
+            data: &DATA[0],
+            left: Box::new(Tree::Node {
+                key: 13,
+                data: &DATA[1],
+                left: Box::new(Tree::Leaf(1, &DATA[2])),
+                right: Box::new(Tree::Leaf(2, &DATA[3])),
+            }),
+            right: Box::new(Tree::Leaf(3, &DATA[4])),
+        }
+    }
 
     #[test]
     fn binary_tree() {
         // Stack allocated data that the tree nodes will point to.
         // The lifetime will be bound to the tree so that references remain valid.
-        let data = vec![
+        let data = [
             "root node",
             "inner node",
             "1st leaf",
@@ -118,26 +702,328 @@ mod test {
         assert_eq!(Some(&"2nd leaf"), tree.search(&2));
         assert_eq!(None, tree.search(&7));
     }
-}
 
-/// This test demonstrates that in Rust all *self-referential* structures must have size known at
-/// compile time. This means that such structures *cannot own* data of type `Self` but rather have
-/// to indirectly refence these via some sort of a pointer.
-///
-/// # Example
-/// ```compile_fail
-/// enum PList<T> {
-///     Nil,
-///     Cons(T, Self),
-/// }
-/// ```
-///
-/// In order to make the example above compile, one would have to use some sort of indirection for
-/// the `Self` owned by the `Cons` variant. This indirection can be realized by either
-///  * a refence to stack-allocated data (`&`)
-///  * a refence to heap-allocated data ([Box])
-///  * a refence counting pointer ([std::rc::Rc])
-///
-/// or similar pointer-like structure which has *defined size* - i.e. is known not to be
-/// infinite (of unbounded memory).
-pub struct SelfReferentialStructureTest;
+    #[rstest]
+    #[case::root(42, 0, Some(&DATA[0]))]
+    #[case::inner_within_limit(13, 1, Some(&DATA[1]))]
+    #[case::leaf_within_limit(2, 2, Some(&DATA[3]))]
+    #[case::leaf_above_limit(3, 5, Some(&DATA[4]))]
+    #[case::inner_beyond_limit(13, 0, None)]
+    #[case::leaf_beyond_limit(2, 1, None)]
+    #[case::missing(7, 5, None)]
+    fn bounded_search(
+        tree: Tree<'static, i32, &'static str>,
+        #[case] key: i32,
+        #[case] max_depth: usize,
+        #[case] expected: Option<&&str>,
+    ) {
+        assert_eq!(tree.search_depth(&key, max_depth), expected);
+    }
+
+    #[rstest]
+    fn binary_search_tree(tree: Tree<'static, i32, &'static str>) {
+        let data = ["one", "two", "three", "four", "five", "six", "seven"];
+
+        let bst = Tree::Node {
+            key: 4,
+            data: &data[3],
+            left: Box::new(Tree::Node {
+                key: 2,
+                data: &data[1],
+                left: Box::new(Tree::Leaf(1, &data[0])),
+                right: Box::new(Tree::Leaf(3, &data[2])),
+            }),
+            right: Box::new(Tree::Node {
+                key: 6,
+                data: &data[5],
+                left: Box::new(Tree::Leaf(5, &data[4])),
+                right: Box::new(Tree::Leaf(7, &data[6])),
+            }),
+        };
+
+        for (key, expected) in (1..=7).zip(data.iter()) {
+            assert_eq!(bst.search_bst(&key), Some(expected));
+            assert_eq!(bst.search_bst(&key), bst.search(&key));
+        }
+        assert_eq!(bst.search_bst(&0), None);
+        assert_eq!(bst.search_bst(&8), None);
+
+        // The fixture is not a BST, so the key can't be found by following the ordering
+        assert_eq!(tree.search(&3), Some(&DATA[4]));
+        assert_eq!(tree.search_bst(&3), None);
+    }
+
+    #[rstest]
+    #[case::single(1, 0)]
+    #[case::three(3, 1)]
+    #[case::five(5, 2)]
+    #[case::full(127, 6)]
+    #[case::odd(1001, 9)]
+    fn balanced_from_sorted(#[case] size: usize, #[case] expected_depth: usize) {
+        let items = (0..size).map(|k| (k, k * 10)).collect::<Vec<_>>();
+
+        let tree = Tree::from_sorted(&items).expect("sorted keys");
+
+        assert_eq!(tree.depth(), expected_depth);
+        assert!(tree.depth() <= 2 * ((size + 1) as f64).log2().ceil() as usize);
+
+        assert_eq!(tree.cata(|_, _| 1, |_, _, l, r| 1 + l + r), size);
+        for (key, data) in items.iter() {
+            assert_eq!(tree.search_bst(key), Some(data));
+        }
+    }
+
+    #[rstest]
+    #[case::empty(vec![])]
+    #[case::even(vec![(1, 1), (2, 2)])]
+    #[case::unsorted(vec![(1, 1), (3, 3), (2, 2)])]
+    #[case::duplicate(vec![(1, 1), (1, 1), (2, 2)])]
+    fn invalid_from_sorted(#[case] items: Vec<(i32, i32)>) {
+        assert!(Tree::from_sorted(&items).is_none());
+    }
+
+    #[rstest]
+    fn tree_equality(tree: Tree<'static, i32, &'static str>) {
+        // Independently constructed tree with the same structure and equal data (stored elsewhere)
+        let data = DATA;
+        let same = Tree::Node {
+            key: 42,
+            data: &data[0],
+            left: Box::new(Tree::Node {
+                key: 13,
+                data: &data[1],
+                left: Box::new(Tree::Leaf(1, &data[2])),
+                right: Box::new(Tree::Leaf(2, &data[3])),
+            }),
+            right: Box::new(Tree::Leaf(3, &data[4])),
+        };
+        assert_eq!(tree, same);
+
+        // Same keys and data but different structure
+        let mirrored = Tree::Node {
+            key: 42,
+            data: &DATA[0],
+            left: Box::new(Tree::Leaf(3, &DATA[4])),
+            right: Box::new(Tree::Node {
+                key: 13,
+                data: &DATA[1],
+                left: Box::new(Tree::Leaf(1, &DATA[2])),
+                right: Box::new(Tree::Leaf(2, &DATA[3])),
+            }),
+        };
+        assert_ne!(tree, mirrored);
+
+        // Same structure and keys but different data
+        let other = Tree::Node {
+            key: 42,
+            data: &DATA[1],
+            left: Box::new(Tree::Node {
+                key: 13,
+                data: &DATA[1],
+                left: Box::new(Tree::Leaf(1, &DATA[2])),
+                right: Box::new(Tree::Leaf(2, &DATA[3])),
+            }),
+            right: Box::new(Tree::Leaf(3, &DATA[4])),
+        };
+        assert_ne!(tree, other);
+
+        // Trees built from sorted items can be compared with hand-made ones
+        let items = [(1, "a"), (2, "b"), (3, "c")];
+        let expected = Tree::Node {
+            key: 2,
+            data: &"b",
+            left: Box::new(Tree::Leaf(1, &"a")),
+            right: Box::new(Tree::Leaf(3, &"c")),
+        };
+        assert_eq!(Tree::from_sorted(&items), Some(expected));
+    }
+
+    #[rstest]
+    fn tree_depth(tree: Tree<'static, i32, &'static str>) {
+        assert_eq!(tree.depth(), 2);
+        assert_eq!(Tree::Leaf(1, &DATA[0]).depth(), 0);
+    }
+
+    #[rstest]
+    fn dot_graph(tree: Tree<'static, i32, &'static str>) {
+        let dot = tree.to_dot();
+
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.ends_with("}\n"));
+
+        let nodes = dot.lines().filter(|l| l.contains("[label=")).count();
+        let edges = dot.lines().filter(|l| l.contains(" -> ")).count();
+        assert_eq!(nodes, 5);
+        assert_eq!(edges, 4);
+
+        for key in [42, 13, 1, 2, 3] {
+            assert!(dot.contains(&format!("[label=\"{}\"]", key)));
+        }
+
+        // Root is connected to the inner node and the 3rd leaf
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(dot.contains("n0 -> n4;"));
+    }
+
+    #[rstest]
+    fn display_sideways(tree: Tree<'static, i32, &'static str>) {
+        let rendered = tree.to_string();
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec!["    3", "42", "        2", "    13", "        1"]
+        );
+    }
+
+    #[rstest]
+    fn counting_visitor(tree: Tree<'static, i32, &'static str>) {
+        let mut visitor = CountingVisitor::default();
+        tree.accept(&mut visitor);
+        assert_eq!(visitor.leaves, 3);
+        assert_eq!(visitor.nodes, 2);
+    }
+
+    #[rstest]
+    fn key_collecting_visitor(tree: Tree<'static, i32, &'static str>) {
+        let mut visitor = KeyCollectingVisitor::default();
+        tree.accept(&mut visitor);
+        assert_eq!(visitor.keys, vec![42, 13, 1, 2, 3]);
+    }
+
+    #[rstest]
+    fn cata_height(tree: Tree<'static, i32, &'static str>) {
+        fn height<K, V>(tree: &Tree<'_, K, V>) -> usize {
+            match tree {
+                Tree::Leaf(_, _) => 1,
+                Tree::Node { left, right, .. } => 1 + height(left).max(height(right)),
+            }
+        }
+
+        let cata_height = tree.cata(|_, _| 1, |_, _, l: usize, r| 1 + l.max(r));
+        assert_eq!(cata_height, height(&tree));
+        assert_eq!(cata_height, 3);
+    }
+
+    #[rstest]
+    fn cata_sum() {
+        fn sum(tree: &Tree<'_, u32, u32>) -> u32 {
+            match tree {
+                Tree::Leaf(_, data) => **data,
+                Tree::Node {
+                    data, left, right, ..
+                } => **data + sum(left) + sum(right),
+            }
+        }
+
+        let data = [1, 2, 3, 4, 5];
+        let tree = Tree::Node {
+            key: 0,
+            data: &data[0],
+            left: Box::new(Tree::Leaf(1, &data[1])),
+            right: Box::new(Tree::Node {
+                key: 2,
+                data: &data[2],
+                left: Box::new(Tree::Leaf(3, &data[3])),
+                right: Box::new(Tree::Leaf(4, &data[4])),
+            }),
+        };
+
+        let cata_sum = tree.cata(|_, v| *v, |_, v, l, r| v + l + r);
+        assert_eq!(cata_sum, sum(&tree));
+        assert_eq!(cata_sum, 15);
+    }
+
+    #[rstest]
+    fn fold_keys(tree: Tree<'static, i32, &'static str>) {
+        assert_eq!(tree.fold(0, |sum, k, _| sum + k), 42 + 13 + 1 + 2 + 3);
+    }
+
+    #[rstest]
+    fn fold_data_in_order(tree: Tree<'static, i32, &'static str>) {
+        let data = tree.fold(String::new(), |mut acc, _, data| {
+            if !acc.is_empty() {
+                acc.push_str(", ");
+            }
+            acc.push_str(data);
+            acc
+        });
+        assert_eq!(
+            data,
+            "1st leaf, inner node, 2nd leaf, root node, 3rd leaf".to_string()
+        );
+    }
+
+    #[rstest]
+    fn map_values() {
+        let data = [1, 2, 3, 4, 5];
+        let tree = Tree::Node {
+            key: 'a',
+            data: &data[0],
+            left: Box::new(Tree::Leaf('b', &data[1])),
+            right: Box::new(Tree::Node {
+                key: 'c',
+                data: &data[2],
+                left: Box::new(Tree::Leaf('d', &data[3])),
+                right: Box::new(Tree::Leaf('e', &data[4])),
+            }),
+        };
+
+        let mapped = tree.map(|v| v * 2);
+
+        let expected = OwnedTree::Node {
+            key: 'a',
+            data: 2,
+            left: Box::new(OwnedTree::Leaf('b', 4)),
+            right: Box::new(OwnedTree::Node {
+                key: 'c',
+                data: 6,
+                left: Box::new(OwnedTree::Leaf('d', 8)),
+                right: Box::new(OwnedTree::Leaf('e', 10)),
+            }),
+        };
+        assert_eq!(mapped, expected);
+
+        let in_order = mapped.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>();
+        assert_eq!(
+            in_order,
+            vec![('b', 4), ('a', 2), ('d', 8), ('c', 6), ('e', 10)]
+        );
+    }
+
+    #[fixture]
+    fn owned_tree() -> OwnedTree<i32, String> {
+        OwnedTree::Node {
+            key: 42,
+            data: "root node".to_string(),
+            left: Box::new(OwnedTree::Node {
+                key: 13,
+                data: "inner node".to_string(),
+                left: Box::new(OwnedTree::Leaf(1, "1st leaf".to_string())),
+                right: Box::new(OwnedTree::Leaf(2, "2nd leaf".to_string())),
+            }),
+            right: Box::new(OwnedTree::Leaf(3, "3rd leaf".to_string())),
+        }
+    }
+
+    #[rstest]
+    #[case::root(42)]
+    #[case::inner(13)]
+    #[case::left_leaf(2)]
+    #[case::right_leaf(3)]
+    fn owned_tree_update(mut owned_tree: OwnedTree<i32, String>, #[case] key: i32) {
+        assert!(owned_tree.update(&key, |data| data.push_str(" (updated)")));
+
+        let data = owned_tree.search(&key).expect("key is present");
+        assert!(data.ends_with(" (updated)"), "{} was not updated", data);
+    }
+
+    #[rstest]
+    fn owned_tree_update_missing(mut owned_tree: OwnedTree<i32, String>) {
+        let original = owned_tree.clone();
+        let mut called = false;
+
+        assert!(!owned_tree.update(&7, |_| called = true));
+        assert!(!called);
+        assert_eq!(owned_tree, original);
+    }
+}
@@ -5,7 +5,8 @@
 //! lifetimes and there is no cost at runtime.
 //!
 //! The example is taken from the [GhostCell paper](http://plv.mpi-sws.org/rustbelt/ghostcell/).
-use std::marker::PhantomData;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
 /// Lifetime wrapper which makes `'id` *invariant* and has no size.
 ///
@@ -117,6 +118,53 @@ impl<'id, T> BrandedVec<'id, T> {
         })
     }
 
+    /// Construct new empty [BrandedVec] with pre-allocated `capacity` and run a closure `f` with
+    /// it (see [BrandedVec::make]).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::brands::BrandedVec;
+    ///
+    /// BrandedVec::make_with_capacity(16, |mut bvec| {
+    ///     let idx = bvec.push(42);
+    ///     assert!(bvec.capacity() >= 16);
+    ///     assert_eq!(bvec.get(idx), &42);
+    /// });
+    /// ```
+    pub fn make_with_capacity<R>(
+        capacity: usize,
+        f: impl for<'a> FnOnce(BrandedVec<'a, T>) -> R,
+    ) -> R {
+        Self::make(Vec::with_capacity(capacity), f)
+    }
+
+    /// Returns the number of elements in this [BrandedVec]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if this [BrandedVec] has no elements
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the number of elements this [BrandedVec] can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more elements (see [Vec::reserve]), so that
+    /// subsequent calls to [`push`](BrandedVec::push) don't reallocate.
+    ///
+    /// The capacity is independent of the length, so this does not affect any [BrandedIndex].
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional);
+    }
+
     /// Appends given `value` to this [BrandedVec] and returns [BrandedIndex] of this item which is
     /// bound to `self`. Since [BrandedVec] can only be appended to, this [BrandedIndex] is
     /// guaranteed to *always* be within bounds.
@@ -142,6 +190,111 @@ impl<'id, T> BrandedVec<'id, T> {
         }
     }
 
+    /// Returns the branded index of the first element or `None` if this [BrandedVec] is empty.
+    #[inline]
+    pub fn first_index(&self) -> Option<BrandedIndex<'id>> {
+        self.get_index(0)
+    }
+
+    /// Returns the branded index of the last element or `None` if this [BrandedVec] is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::brands::BrandedVec;
+    ///
+    /// BrandedVec::make(vec![1, 2, 3], |bvec| {
+    ///     let (first, last) = bvec.first_index().zip(bvec.last_index()).expect("non-empty");
+    ///     assert_eq!((bvec.get(first), bvec.get(last)), (&1, &3));
+    /// });
+    /// ```
+    #[inline]
+    pub fn last_index(&self) -> Option<BrandedIndex<'id>> {
+        self.inner
+            .len()
+            .checked_sub(1)
+            .and_then(|idx| self.get_index(idx))
+    }
+
+    /// Iterates over all contiguous windows of `size` branded indices (overlapping, in order).
+    ///
+    /// Just like [slice::windows], there are no windows if `size` is larger than the length of
+    /// this vector. Each index carries the brand `'id`, so reading the window's values via
+    /// [`get`](BrandedVec::get) requires no further bounds checks.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::brands::BrandedVec;
+    ///
+    /// BrandedVec::make(vec![1, 2, 3, 4], |bvec| {
+    ///     let sums = bvec
+    ///         .windows(2)
+    ///         .map(|w| w.into_iter().map(|i| bvec.get(i)).sum::<i32>())
+    ///         .collect::<Vec<_>>();
+    ///     assert_eq!(sums, vec![3, 5, 7]);
+    /// });
+    /// ```
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = Vec<BrandedIndex<'id>>> {
+        assert!(size != 0, "window size must be non-zero");
+        let _marker = self._marker;
+        let count = (self.inner.len() + 1).saturating_sub(size);
+        (0..count).map(move |start| {
+            (start..start + size)
+                .map(|idx| BrandedIndex { idx, _marker })
+                .collect()
+        })
+    }
+
+    /// Iterates over non-overlapping chunks of `size` branded indices (in order).
+    ///
+    /// Just like [slice::chunks], the last chunk is shorter if `size` does not divide the length
+    /// of this vector.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = Vec<BrandedIndex<'id>>> {
+        assert!(size != 0, "chunk size must be non-zero");
+        let _marker = self._marker;
+        let len = self.inner.len();
+        (0..len).step_by(size).map(move |start| {
+            (start..len.min(start + size))
+                .map(|idx| BrandedIndex { idx, _marker })
+                .collect()
+        })
+    }
+
+    /// Validates the range `[start, end)` against this [BrandedVec] and if it's within bounds,
+    /// returns a [BrandedSlice] view of it which carries the same brand `'id`.
+    ///
+    /// This is the only check against the bounds of the parent vector, the slice then only
+    /// checks that an index falls within the range (see [BrandedSlice::get]).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::brands::BrandedVec;
+    ///
+    /// BrandedVec::make(vec![10, 11, 12, 13], |bvec| {
+    ///     assert!(bvec.get_range(2, 5).is_none());
+    ///
+    ///     let slice = bvec.get_range(1, 3).expect("range within bounds");
+    ///     let values = slice.indices().filter_map(|i| slice.get(i)).collect::<Vec<_>>();
+    ///     assert_eq!(values, vec![&11, &12]);
+    /// });
+    /// ```
+    pub fn get_range(&self, start: usize, end: usize) -> Option<BrandedSlice<'_, 'id, T>> {
+        if start <= end && end <= self.inner.len() {
+            Some(BrandedSlice {
+                inner: &self.inner[start..end],
+                start,
+                _marker: self._marker,
+            })
+        } else {
+            None
+        }
+    }
+
     /// Get shared reference to the interior value at given [BrandedIndex] without performing a
     /// bounds check.
     pub fn get(&self, index: BrandedIndex<'id>) -> &T {
@@ -157,4 +310,478 @@ impl<'id, T> BrandedVec<'id, T> {
         // only be appended to (`BrandedIndex` is monotonic)
         unsafe { self.inner.get_unchecked_mut(index.idx) }
     }
+
+    /// Transforms each element of this [BrandedVec] with `f` while preserving the brand `'id`.
+    ///
+    /// This is safe because mapping does not change the length of the vector. Since a
+    /// [BrandedIndex] only certifies that an index is within bounds, every index issued for
+    /// `self` is also valid for the mapped vector.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::brands::BrandedVec;
+    ///
+    /// BrandedVec::make(vec![1, 2], |mut bvec| {
+    ///     let idx = bvec.push(3);
+    ///
+    ///     let bvec = bvec.map(|x| x.to_string());
+    ///
+    ///     // The index obtained before the map is still valid
+    ///     assert_eq!(bvec.get(idx), "3");
+    /// });
+    /// ```
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> BrandedVec<'id, U> {
+        BrandedVec {
+            inner: self.inner.into_iter().map(f).collect(),
+            _marker: self._marker,
+        }
+    }
+
+    /// Shortens this vector to `len` elements and runs closure `f` with the result branded by a
+    /// *fresh* brand (if `len` is greater than the current length, this has no effect).
+    ///
+    /// Removing elements breaks the monotonicity which [BrandedIndex] relies on, so the
+    /// truncated vector must not accept any index issued for `self`. Since a fresh brand can only
+    /// be introduced by a rank-2 closure (see [BrandedVec::make]), this method is written in the
+    /// same *continuation-passing style*. Signature like `truncate(self, len) -> BrandedVec<'new,
+    /// T>` would not work because then the caller could simply pick `'new = 'id`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::brands::BrandedVec;
+    ///
+    /// BrandedVec::make(vec![1, 2, 3], |bvec| {
+    ///     bvec.truncate(1, |mut bvec| {
+    ///         let idx = bvec.push(4);
+    ///         assert_eq!(bvec.get(idx), &4);
+    ///     });
+    /// });
+    /// ```
+    ///
+    /// Indices issued before the truncation are statically rejected.
+    /// ```compile_fail
+    /// use rust_examples::brands::BrandedVec;
+    ///
+    /// BrandedVec::make(vec![1, 2, 3], |bvec| {
+    ///     let idx = bvec.get_index(2).unwrap();
+    ///
+    ///     bvec.truncate(1, move |bvec| {
+    ///         // Would be out of bounds, but the brand doesn't match
+    ///         bvec.get(idx);
+    ///     });
+    /// });
+    /// ```
+    pub fn truncate<R>(self, len: usize, f: impl for<'a> FnOnce(BrandedVec<'a, T>) -> R) -> R {
+        let mut inner = self.inner;
+        inner.truncate(len);
+        BrandedVec::make(inner, f)
+    }
+
+    /// Consumes this [BrandedVec] and returns the inner [Vec].
+    ///
+    /// Note that this forgets the brand, so any [BrandedIndex] issued for `self` can no longer be
+    /// used to access the data.
+    pub fn into_vec(self) -> Vec<T> {
+        self.inner
+    }
+}
+
+impl<'id, T: Ord> BrandedVec<'id, T> {
+    /// Returns the branded index of the maximal element (the last one if there are several) or
+    /// `None` if this [BrandedVec] is empty.
+    ///
+    /// The index can then be used to access the element without a bounds check, e.g. to modify it
+    /// in place via [`get_mut`](BrandedVec::get_mut).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::brands::BrandedVec;
+    ///
+    /// BrandedVec::make(vec![3, 7, 5], |mut bvec| {
+    ///     let max = bvec.arg_max().expect("non-empty vector");
+    ///     *bvec.get_mut(max) = 0;
+    ///     assert_eq!(bvec.into_vec(), vec![3, 0, 5]);
+    /// });
+    /// ```
+    pub fn arg_max(&self) -> Option<BrandedIndex<'id>> {
+        let (idx, _) = self.inner.iter().enumerate().max_by_key(|&(_, x)| x)?;
+        Some(BrandedIndex {
+            idx,
+            _marker: self._marker,
+        })
+    }
+
+    /// Returns the branded index of the minimal element (the first one if there are several) or
+    /// `None` if this [BrandedVec] is empty.
+    pub fn arg_min(&self) -> Option<BrandedIndex<'id>> {
+        let (idx, _) = self.inner.iter().enumerate().min_by_key(|&(_, x)| x)?;
+        Some(BrandedIndex {
+            idx,
+            _marker: self._marker,
+        })
+    }
+}
+
+/// Contiguous range `[start, end)` of a [BrandedVec] which carries the same brand `'id`.
+///
+/// The range has been validated when the slice was created (see [BrandedVec::get_range]) and the
+/// slice only references the elements within it. Indices of the slice are branded for the parent
+/// [BrandedVec], so they can be used with it as well, but a [BrandedIndex] of the parent which
+/// lies outside of `[start, end)` cannot read through the slice.
+pub struct BrandedSlice<'a, 'id, T> {
+    inner: &'a [T],
+    start: usize,
+    _marker: InvariantLifetime<'id>,
+}
+
+impl<'a, 'id, T> BrandedSlice<'a, 'id, T> {
+    /// Number of elements in this slice
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if this slice has no elements
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Brands the element at relative `offset` within this slice if `offset < len`.
+    pub fn index(&self, offset: usize) -> Option<BrandedIndex<'id>> {
+        if offset < self.len() {
+            Some(BrandedIndex {
+                idx: self.start + offset,
+                _marker: self._marker,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over branded indices of all elements in this slice (in order).
+    ///
+    /// No bounds checks are necessary since the whole range has already been validated.
+    pub fn indices(&self) -> impl Iterator<Item = BrandedIndex<'id>> {
+        let _marker = self._marker;
+        (self.start..self.start + self.len()).map(move |idx| BrandedIndex { idx, _marker })
+    }
+
+    /// Get shared reference to the interior value at given [BrandedIndex] if it lies within the
+    /// range of this slice, otherwise returns [None].
+    ///
+    /// The index is translated to an offset relative to `start` and, once it's known to be within
+    /// the range, the access itself is unchecked.
+    pub fn get(&self, index: BrandedIndex<'id>) -> Option<&'a T> {
+        let offset = index.idx.checked_sub(self.start)?;
+        if offset < self.inner.len() {
+            // Safety: The offset has just been checked to be within the bounds of the slice
+            Some(unsafe { self.inner.get_unchecked(offset) })
+        } else {
+            None
+        }
+    }
+}
+
+/// [BrandedVec] is serialized just as the inner [Vec] since the brand `'id` has no runtime
+/// representation.
+///
+/// Note that serialization of [BrandedIndex] is intentionally not supported. An index is only
+/// valid for the particular instance it was branded for and a deserialized vector always gets a
+/// fresh brand (see [BrandedVec::deserialize_make]), so all indices must be re-branded anyway
+/// (e.g. via [BrandedVec::get_index]).
+#[cfg(feature = "serde")]
+impl<'id, T: serde::Serialize> serde::Serialize for BrandedVec<'id, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'id, T> BrandedVec<'id, T> {
+    /// Deserializes the inner [Vec] and runs closure `f` with a freshly branded [BrandedVec].
+    ///
+    /// This is an analogy to [BrandedVec::make] and a counterpart to the [serde::Serialize]
+    /// implementation. It's not possible to implement [serde::Deserialize] for [BrandedVec]
+    /// itself because the brand can only be created by the rank-2 closure.
+    pub fn deserialize_make<'de, D, R>(
+        deserializer: D,
+        f: impl for<'a> FnOnce(BrandedVec<'a, T>) -> R,
+    ) -> Result<R, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        T: serde::Deserialize<'de>,
+    {
+        use serde::Deserialize;
+        Vec::deserialize(deserializer).map(|inner| Self::make(inner, f))
+    }
+}
+
+/// This test demonstrates that a [BrandedIndex] cannot escape the closure passed to
+/// [BrandedVec::make] (or any other brand-introducing method such as [BrandedVec::truncate]).
+///
+/// If it could, the index might later be used with another [BrandedVec] and because the access is
+/// unchecked, this would be an out-of-bounds read. However, the result type `R` of `make` is
+/// chosen *outside* of the rank-2 closure, so it cannot mention the fresh brand `'a`. The brand is
+/// *invariant*, so it can't be shortened or extended to some other lifetime either.
+///
+/// # Example 1
+/// Returning a branded index out of the closure does not compile:
+/// ```compile_fail
+/// use rust_examples::brands::BrandedVec;
+///
+/// let idx = BrandedVec::make(vec![1, 2, 3], |mut bvec| bvec.push(4));
+///
+/// BrandedVec::make(vec![1], move |bvec| {
+///     // Would read past the end of the single-element vector
+///     bvec.get(idx);
+/// });
+/// ```
+///
+/// # Example 2
+/// Neither does storing the branded index in a variable captured from the outer scope:
+/// ```compile_fail
+/// use rust_examples::brands::{BrandedIndex, BrandedVec};
+///
+/// let mut escaped: Option<BrandedIndex<'_>> = None;
+///
+/// BrandedVec::make(vec![1, 2, 3], |mut bvec| {
+///     escaped = Some(bvec.push(4));
+/// });
+/// ```
+///
+/// # Example 3
+/// On the other hand, anything which does not carry the brand (such as the value behind an index)
+/// can be returned freely.
+/// ```
+/// use rust_examples::brands::BrandedVec;
+///
+/// let value = BrandedVec::make(vec![1, 2, 3], |mut bvec| {
+///     let idx = bvec.push(4);
+///     *bvec.get(idx)
+/// });
+/// assert_eq!(value, 4);
+/// ```
+pub struct BrandIndexEscapeTest;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_vec() {
+        let vec = BrandedVec::make(vec![1, 2], |mut bvec| {
+            bvec.push(3);
+            bvec.into_vec()
+        });
+        assert_eq!(vec, vec![1, 2, 3]);
+    }
+
+    /// Branded (unchecked) access agrees with the checked indexing of a plain [Vec] for any
+    /// initial `items` and `pushed` elements, while any index out of bounds is rejected.
+    #[quickcheck]
+    fn branded_access_matches_vec(items: Vec<i32>, pushed: Vec<i32>, offset: usize) -> bool {
+        let expected = items
+            .iter()
+            .chain(pushed.iter())
+            .copied()
+            .collect::<Vec<_>>();
+
+        BrandedVec::make(items, |mut bvec| {
+            let pushed_ok = pushed.iter().all(|&x| {
+                let idx = bvec.push(x);
+                *bvec.get(idx) == x
+            });
+
+            let indexed_ok = (0..expected.len()).all(|i| {
+                bvec.get_index(i)
+                    .is_some_and(|idx| *bvec.get(idx) == expected[i])
+            });
+
+            let out_of_bounds = expected.len().saturating_add(offset);
+
+            pushed_ok && indexed_ok && bvec.get_index(out_of_bounds).is_none()
+        })
+    }
+
+    #[test]
+    fn reserve_capacity() {
+        BrandedVec::make(vec![1, 2, 3], |mut bvec| {
+            let idx = bvec.last_index().expect("non-empty vector");
+
+            bvec.reserve(100);
+            assert!(bvec.capacity() >= 103);
+            assert_eq!(bvec.len(), 3);
+
+            // Indices issued before the reservation are still valid
+            assert_eq!(bvec.get(idx), &3);
+        });
+
+        BrandedVec::make_with_capacity(10, |mut bvec| {
+            assert!(bvec.capacity() >= 10);
+            assert!(bvec.is_empty());
+
+            bvec.push(1);
+            assert_eq!(bvec.len(), 1);
+            assert!(bvec.capacity() >= 10);
+        });
+    }
+
+    #[test]
+    fn map_preserves_brand() {
+        let vec = BrandedVec::make(vec![1, 2, 3], |bvec| {
+            let first = bvec.first_index().expect("non-empty vector");
+            let last = bvec.last_index().expect("non-empty vector");
+
+            let mut bvec = bvec.map(|x| format!("#{}", x));
+
+            // Indices issued before the map are still valid for the mapped vector
+            assert_eq!(bvec.get(first), "#1");
+            bvec.get_mut(last).push('!');
+
+            bvec.into_vec()
+        });
+        assert_eq!(vec, vec!["#1", "#2", "#3!"]);
+    }
+
+    #[test]
+    fn arg_max_and_min() {
+        let vec = BrandedVec::make(vec![3, 1, 7, 5, 1], |mut bvec| {
+            let max = bvec.arg_max().expect("non-empty vector");
+            let min = bvec.arg_min().expect("non-empty vector");
+
+            // Double the maximum in place
+            *bvec.get_mut(max) *= 2;
+            assert_eq!(bvec.get(max), &14);
+            assert_eq!(bvec.get(min), &1);
+
+            bvec.into_vec()
+        });
+        assert_eq!(vec, vec![3, 1, 14, 5, 1]);
+
+        BrandedVec::make(Vec::<i32>::new(), |bvec| {
+            assert!(bvec.arg_max().is_none());
+            assert!(bvec.arg_min().is_none());
+        });
+    }
+
+    #[test]
+    fn truncate_rebrands() {
+        let vec = BrandedVec::make(vec![1, 2, 3, 4], |bvec| {
+            bvec.truncate(2, |mut bvec| {
+                assert!(bvec.get_index(2).is_none());
+
+                let i = bvec.get_index(1).expect("index within bounds");
+                let j = bvec.push(5);
+
+                *bvec.get_mut(i) *= 10;
+                assert_eq!(bvec.get(i), &20);
+                assert_eq!(bvec.get(j), &5);
+
+                bvec.into_vec()
+            })
+        });
+        assert_eq!(vec, vec![1, 20, 5]);
+    }
+
+    #[test]
+    fn branded_slice() {
+        BrandedVec::make(vec![10, 11, 12, 13], |bvec| {
+            assert!(bvec.get_range(3, 5).is_none());
+            assert!(bvec.get_range(3, 2).is_none());
+            assert!(bvec.get_range(4, 4).expect("empty range").is_empty());
+
+            let slice = bvec.get_range(1, 4).expect("range within bounds");
+            assert_eq!(slice.len(), 3);
+            assert!(slice.index(3).is_none());
+
+            let first = slice.index(0).expect("offset within slice");
+            let last = slice.index(2).expect("offset within slice");
+            assert_eq!(slice.get(first), Some(&11));
+            assert_eq!(slice.get(last), Some(&13));
+
+            // Indices of a slice are valid for the parent vector as well
+            assert_eq!(bvec.get(last), &13);
+
+            let values = slice
+                .indices()
+                .filter_map(|i| slice.get(i))
+                .collect::<Vec<_>>();
+            assert_eq!(values, vec![&11, &12, &13]);
+        });
+    }
+
+    #[test]
+    fn branded_slice_rejects_outer_indices() {
+        BrandedVec::make(vec![10, 11, 12, 13, 14], |bvec| {
+            let slice = bvec.get_range(2, 4).expect("range within bounds");
+
+            // Indices of the parent vector outside of the range cannot read through the slice
+            let before = bvec.first_index().expect("non-empty vec");
+            let after = bvec.get_index(4).expect("index within bounds");
+            assert_eq!(slice.get(before), None);
+            assert_eq!(slice.get(after), None);
+
+            let inside = bvec.get_index(3).expect("index within bounds");
+            assert_eq!(slice.get(inside), Some(&13));
+        });
+    }
+
+    #[test]
+    fn first_and_last_index() {
+        BrandedVec::make(vec![10, 11, 12], |mut bvec| {
+            let first = bvec.first_index().expect("non-empty vector");
+            let last = bvec.last_index().expect("non-empty vector");
+            assert_eq!(bvec.get(first), &10);
+            assert_eq!(bvec.get(last), &12);
+
+            let pushed = bvec.push(13);
+            let last = bvec.last_index().expect("non-empty vector");
+            assert_eq!(bvec.get(last), bvec.get(pushed));
+        });
+
+        BrandedVec::make(Vec::<i32>::new(), |bvec| {
+            assert!(bvec.first_index().is_none());
+            assert!(bvec.last_index().is_none());
+        });
+    }
+
+    #[test]
+    fn windows_and_chunks() {
+        BrandedVec::make(vec![1, 2, 3, 4, 5], |bvec| {
+            let windows = bvec
+                .windows(3)
+                .map(|w| w.into_iter().map(|i| *bvec.get(i)).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            assert_eq!(windows, vec![vec![1, 2, 3], vec![2, 3, 4], vec![3, 4, 5]]);
+            assert_eq!(bvec.windows(6).count(), 0);
+
+            let chunks = bvec
+                .chunks(2)
+                .map(|c| c.into_iter().map(|i| *bvec.get(i)).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let json = BrandedVec::make(vec![1, 2], |mut bvec| {
+            bvec.push(3);
+            serde_json::to_string(&bvec).expect("serializable vector")
+        });
+
+        assert_eq!(json, "[1,2,3]");
+
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let last = BrandedVec::deserialize_make(&mut deserializer, |bvec: BrandedVec<'_, i32>| {
+            // Indices must be re-branded for the deserialized vector
+            let idx = bvec.get_index(2).expect("index within bounds");
+            *bvec.get(idx)
+        })
+        .expect("deserializable vector");
+
+        assert_eq!(last, 3);
+    }
 }
@@ -7,7 +7,9 @@
 //! Rust implements pattern via combination of `map` (to apply the effect) followed by `collect`
 //! which is available for [std::iter::FromIterator] instances (in Scala Cats this is referred to
 //! as `sequence`).
+use std::collections::HashMap;
 use std::fs::File;
+use std::hash::Hash;
 use std::io::prelude::*;
 use std::io::{BufReader, Result};
 use std::path::Path;
@@ -33,6 +35,147 @@ fn first(input: &str) -> Option<char> {
     input.chars().next()
 }
 
+/// Turn a collection of [Option]s "inside out" - the `sequence` operation.
+///
+/// This is a traversal with the identity function, i.e. the effects are already there and are
+/// just sequenced by `collect`. Conversely, `traverse(f)` is `map(f)` followed by `sequence`.
+///
+/// # Example
+/// ```
+/// use rust_examples::collect::sequence_opt;
+///
+/// assert_eq!(sequence_opt(vec![Some(1), Some(2)]), Some(vec![1, 2]));
+/// assert_eq!(sequence_opt(vec![Some(1), None]), None);
+/// ```
+pub fn sequence_opt<T>(v: Vec<Option<T>>) -> Option<Vec<T>> {
+    v.into_iter().collect()
+}
+
+/// Turn a collection of [Result]s "inside out" - the `sequence` operation (see [sequence_opt]).
+///
+/// The result is either all the values or the first error.
+///
+/// # Example
+/// ```
+/// use rust_examples::collect::sequence_res;
+///
+/// assert_eq!(sequence_res::<_, &str>(vec![Ok(1), Ok(2)]), Ok(vec![1, 2]));
+/// assert_eq!(sequence_res(vec![Ok(1), Err("a"), Err("b")]), Err("a"));
+/// ```
+pub fn sequence_res<T, E>(v: Vec<std::result::Result<T, E>>) -> std::result::Result<Vec<T>, E> {
+    v.into_iter().collect()
+}
+
+/// Traverse a collection of *optional* names and take the first character of each.
+///
+/// There are now two nested effects - a name might be absent and a present name might be empty.
+/// Both are [Option]s, so they can be *flattened* (monadic `bind`, which is `and_then` in Rust)
+/// into a single effect per item which is then sequenced by `collect` as in [collect_initials].
+///
+/// # Example
+/// ```
+/// use rust_examples::collect::collect_flatten;
+///
+/// assert_eq!(collect_flatten(vec![Some("Alice"), Some("Bob")]), Some(vec!['A', 'B']));
+/// assert_eq!(collect_flatten(vec![Some("Alice"), None]), None);
+/// ```
+pub fn collect_flatten(names: Vec<Option<&str>>) -> Option<Vec<char>> {
+    names.into_iter().map(|name| name.and_then(first)).collect()
+}
+
+/// Traverse a collection with an effect `f` which additionally depends on the position of each
+/// item.
+///
+/// This is the same pattern as in [collect_initials], only the effect is applied to pairs
+/// `(index, item)` produced by [Iterator::enumerate].
+///
+/// # Example
+/// Collect initials but reject any name which is shorter than its index.
+/// ```
+/// use rust_examples::collect::collect_indexed;
+///
+/// let initial = |i: usize, name: &str| if name.len() < i { None } else { name.chars().next() };
+///
+/// assert_eq!(collect_indexed(vec!["Al", "Bo", "Cid"], initial), Some(vec!['A', 'B', 'C']));
+/// assert_eq!(collect_indexed(vec!["Al", "Bo", "C"], initial), None);
+/// ```
+pub fn collect_indexed<T, U, F>(items: Vec<T>, mut f: F) -> Option<Vec<U>>
+where
+    F: FnMut(usize, T) -> Option<U>,
+{
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| f(i, item))
+        .collect()
+}
+
+/// Traverse the values of a [HashMap] with a fallible effect `f` while preserving the keys.
+///
+/// The traversal is not limited to sequences - `collect` works for any [std::iter::FromIterator]
+/// instance and a [HashMap] is one for `(key, value)` pairs. So the effect is applied to each
+/// value, paired back with its key and the results are sequenced into `Result<HashMap<K, W>, E>`,
+/// short-circuiting on the first error.
+///
+/// Note that the iteration order of a [HashMap] is unspecified, so if several values fail, it's
+/// unspecified which of the errors is returned.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use rust_examples::collect::traverse_map_values;
+///
+/// let map = HashMap::from([("a", "1"), ("b", "2")]);
+/// let parsed = traverse_map_values(map, |v| v.parse::<i32>()).expect("all values are numbers");
+/// assert_eq!(parsed, HashMap::from([("a", 1), ("b", 2)]));
+/// ```
+pub fn traverse_map_values<K, V, W, E, F>(
+    map: HashMap<K, V>,
+    mut f: F,
+) -> std::result::Result<HashMap<K, W>, E>
+where
+    K: Eq + Hash,
+    F: FnMut(V) -> std::result::Result<W, E>,
+{
+    map.into_iter().map(|(k, v)| f(v).map(|w| (k, w))).collect()
+}
+
+/// Lazy counterpart of the traversal which threads the effect of `f` through `iter` one item at a
+/// time instead of collecting all the results eagerly.
+///
+/// The returned iterator yields the results of `f` in order and stops right after the first
+/// error, so just like `collect` into a [Result], no item after the first error is ever evaluated.
+/// However, the caller decides what to do with the results - it might stream them, or stop even
+/// earlier (e.g. with [Iterator::take]) in which case the remaining items are not evaluated either.
+///
+/// # Example
+/// ```
+/// use rust_examples::collect::traverse_lazy;
+///
+/// let parsed = traverse_lazy(vec!["1", "2", "x", "4"], |s| s.parse::<i32>()).collect::<Vec<_>>();
+///
+/// assert_eq!(parsed.len(), 3);
+/// assert_eq!(parsed[..2], [Ok(1), Ok(2)]);
+/// assert!(parsed[2].is_err());
+/// ```
+pub fn traverse_lazy<I, F, U, E>(
+    iter: I,
+    mut f: F,
+) -> impl Iterator<Item = std::result::Result<U, E>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> std::result::Result<U, E>,
+{
+    iter.into_iter().scan(false, move |failed, item| {
+        if *failed {
+            return None;
+        }
+        let result = f(item);
+        *failed = result.is_err();
+        Some(result)
+    })
+}
+
 /// Traversing a [Result] works analogously to an [Option] since a result is basically an option
 /// where the `None` case is some more specific type.
 ///
@@ -44,6 +187,65 @@ fn first(input: &str) -> Option<char> {
 pub fn read_files<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<String>> {
     paths.iter().map(read_file).collect()
 }
+
+/// Reads each file given by `paths` and immediately transforms its contents with `f`, failing on
+/// the first IO error.
+///
+/// This fuses the effectful read with a pure transformation into a single traversal, so there's
+/// no intermediate `Vec<String>` to iterate over again (compare with [read_files]).
+///
+/// # Example
+/// Count lines in each file.
+/// ```no_run
+/// use rust_examples::collect::read_and_map;
+///
+/// let line_counts = read_and_map(&["a.txt", "b.txt"], |text| text.lines().count());
+/// ```
+pub fn read_and_map<P, T, F>(paths: &[P], mut f: F) -> Result<Vec<T>>
+where
+    P: AsRef<Path>,
+    F: FnMut(String) -> T,
+{
+    paths.iter().map(|p| read_file(p).map(&mut f)).collect()
+}
+
+/// Parallel version of [read_files] which reads the files using [rayon](https://docs.rs/rayon).
+///
+/// The resulting contents are in the same order as the input `paths` and if reading any of the
+/// files fails, the error of the first failing path (in the order of `paths`) is returned, just
+/// like with [read_files].
+///
+/// Note that collecting the parallel iterator directly into a [Result] (rayon implements the
+/// parallel analogy of [std::iter::FromIterator] for it) would return whichever error happens
+/// first *in time*. Therefore all the files are read in parallel into a [Vec] of results first
+/// and only then sequenced in order. The price is that remaining reads are not cancelled on error.
+#[cfg(feature = "rayon")]
+pub fn read_files_par<P: AsRef<Path> + Sync>(paths: &[P]) -> Result<Vec<String>> {
+    use rayon::prelude::*;
+    let results = paths.par_iter().map(read_file).collect::<Vec<_>>();
+    results.into_iter().collect()
+}
+
+/// Count non-empty lines across all the files given by `paths`, failing on the first IO error.
+///
+/// A line is empty only if it has no characters at all, so e.g. a whitespace-only line is counted.
+///
+/// Contrary to [read_files], the contents are not read into memory as a whole but streamed line
+/// by line through a [BufReader]. Each line read is an effect (it may fail) and the traversal is
+/// done by [Iterator::sum] which, similarly to `collect`, is implemented for [Result] and
+/// short-circuits on the first error (both within a file and across files).
+pub fn count_nonempty_lines<P: AsRef<Path>>(paths: &[P]) -> Result<usize> {
+    paths.iter().map(count_nonempty_file_lines).sum()
+}
+
+fn count_nonempty_file_lines<P: AsRef<Path>>(path: P) -> Result<usize> {
+    let file = File::open(path)?;
+    BufReader::new(file)
+        .lines()
+        .map(|line| line.map(|line| usize::from(!line.is_empty())))
+        .sum()
+}
+
 fn read_file<P: AsRef<Path>>(path: P) -> Result<String> {
     let file = File::open(path)?;
     let mut buf_reader = BufReader::new(file);
@@ -98,6 +300,119 @@ mod tests {
         assert_eq!(failure, None);
     }
 
+    #[rstest]
+    fn sequence_options() {
+        let success = sequence_opt(vec![Some('A'), Some('B'), Some('C')]);
+        assert_eq!(success, Some(vec!['A', 'B', 'C']));
+
+        let failure = sequence_opt(vec![Some('M'), None, Some('C')]);
+        assert_eq!(failure, None);
+
+        assert_eq!(sequence_opt::<char>(vec![]), Some(vec![]));
+    }
+
+    #[rstest]
+    fn sequence_results() {
+        let success = sequence_res::<_, String>(vec![Ok(1), Ok(2), Ok(3)]);
+        assert_eq!(success, Ok(vec![1, 2, 3]));
+
+        // The first error is returned
+        let failure = sequence_res(vec![Ok(1), Err("first"), Ok(3), Err("second")]);
+        assert_eq!(failure, Err("first"));
+    }
+
+    /// Traversal of all-`Some` effects is `Some` of all the values (in order).
+    #[quickcheck]
+    fn traverse_all_some(names: Vec<String>) -> bool {
+        let names = names
+            .iter()
+            .map(String::as_str)
+            .filter(|n| !n.is_empty())
+            .collect::<Vec<_>>();
+
+        let expected = names.iter().filter_map(|n| n.chars().next()).collect();
+
+        collect_initials(names) == Some(expected)
+    }
+
+    /// Single `None` effect forces the whole traversal to be `None`, regardless of its position.
+    #[quickcheck]
+    fn traverse_any_none(names: Vec<String>, position: usize) -> bool {
+        let mut names = names.iter().map(String::as_str).collect::<Vec<_>>();
+        names.insert(position % (names.len() + 1), "");
+        collect_initials(names).is_none()
+    }
+
+    #[rstest]
+    fn traverse_map() {
+        let map = HashMap::from([("a", "1"), ("b", "2"), ("c", "3")]);
+        let doubled = traverse_map_values(map, |v| v.parse::<i32>().map(|x| 2 * x));
+        assert_eq!(doubled, Ok(HashMap::from([("a", 2), ("b", 4), ("c", 6)])));
+
+        let map = HashMap::from([("a", "1"), ("b", "two"), ("c", "3")]);
+        let parsed = traverse_map_values(map, |v| v.parse::<i32>());
+        assert!(parsed.is_err());
+
+        let empty = traverse_map_values(HashMap::<&str, &str>::new(), |v| v.parse::<i32>());
+        assert_eq!(empty, Ok(HashMap::new()));
+    }
+
+    #[rstest]
+    fn traverse_lazy_short_circuits() {
+        use std::cell::Cell;
+
+        let evaluated = Cell::new(0);
+        let parse = |s: &str| {
+            evaluated.set(evaluated.get() + 1);
+            s.parse::<i32>()
+        };
+
+        let items = vec!["1", "x", "3", "4"];
+
+        // Items after the first error are never evaluated
+        let results = traverse_lazy(items.clone(), parse).collect::<Vec<_>>();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0], Ok(1));
+        assert!(results[1].is_err());
+        assert_eq!(evaluated.get(), 2);
+
+        // Nothing is evaluated until the consumer asks for it and it can stop even earlier
+        evaluated.set(0);
+        let mut lazy = traverse_lazy(items, parse);
+        assert_eq!(evaluated.get(), 0);
+        assert_eq!(lazy.next(), Some(Ok(1)));
+        drop(lazy);
+        assert_eq!(evaluated.get(), 1);
+    }
+
+    #[rstest]
+    #[case::all_present(vec![Some("Alice"), Some("Bob"), Some("Charlie")], Some(vec!['A', 'B', 'C']))]
+    #[case::absent(vec![Some("Alice"), None, Some("Charlie")], None)]
+    #[case::empty(vec![Some("Alice"), Some(""), Some("Charlie")], None)]
+    #[case::no_names(vec![], Some(vec![]))]
+    fn traverse_flatten(#[case] names: Vec<Option<&str>>, #[case] expected: Option<Vec<char>>) {
+        assert_eq!(collect_flatten(names), expected);
+    }
+
+    #[rstest]
+    fn traverse_with_index() {
+        // Name at index `i` must have at least `i` characters
+        let initial = |i: usize, name: &str| {
+            if name.chars().count() < i {
+                None
+            } else {
+                first(name)
+            }
+        };
+
+        let success = collect_indexed(vec!["Alice", "Bob", "Al", "Dan"], initial);
+        assert_eq!(success, Some(vec!['A', 'B', 'A', 'D']));
+
+        // "Al" is shorter than its index 3
+        let failure = collect_indexed(vec!["Alice", "Bob", "Charlie", "Al"], initial);
+        assert_eq!(failure, None);
+    }
+
     #[rstest]
     fn traverse_results(
         #[from(temp_file)]
@@ -119,6 +434,93 @@ mod tests {
         assert!(failure.is_err());
     }
 
+    #[rstest]
+    fn traverse_and_map(
+        #[from(temp_file)]
+        #[with("test_map1", "one\ntwo\nthree")]
+        tmp1: TempFile,
+        #[from(temp_file)]
+        #[with("test_map2", "single line\n")]
+        tmp2: TempFile,
+    ) {
+        let counts = read_and_map(&[tmp1.borrow(), tmp2.borrow()], |text| text.lines().count())
+            .expect("This case should return Ok");
+        assert_eq!(counts, vec![3, 1]);
+
+        let non_existing = TempFile(PathBuf::from("non_existing_file"));
+        let mut calls = 0;
+        let failure = read_and_map(&[non_existing, tmp1, tmp2], |_| calls += 1);
+        assert!(failure.is_err());
+        assert_eq!(calls, 0);
+    }
+
+    #[rstest]
+    fn count_lines(
+        #[from(temp_file)]
+        #[with("test_lines1", "first\n\nsecond\nthird\n")]
+        tmp1: TempFile,
+        #[from(temp_file)]
+        #[with("test_lines2", "\n  \nfourth\n\nfifth")]
+        tmp2: TempFile,
+    ) {
+        // Note that the whitespace-only line is not empty, so it's counted
+        let count = count_nonempty_lines(&[tmp1.borrow(), tmp2.borrow()])
+            .expect("This case should return Ok");
+        assert_eq!(count, 6);
+
+        let non_existing = TempFile(PathBuf::from("non_existing_file"));
+        assert!(count_nonempty_lines(&[tmp1, non_existing, tmp2]).is_err());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[rstest]
+    fn traverse_results_in_parallel() {
+        let files = (0..8)
+            .map(|i| temp_file(&format!("test_par{}", i), &format!("text {}", i)))
+            .collect::<Vec<_>>();
+
+        let success = read_files_par(&files).expect("This case should return Ok");
+        let expected = (0..8).map(|i| format!("text {}", i)).collect::<Vec<_>>();
+        assert_eq!(success, expected);
+
+        let non_existing = [PathBuf::from("non_existing_file")];
+        let paths = files
+            .iter()
+            .map(|f| f.0.clone())
+            .chain(non_existing)
+            .collect::<Vec<_>>();
+        assert!(read_files_par(&paths).is_err());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[rstest]
+    fn parallel_traversal_returns_first_error_in_order() {
+        use std::io::ErrorKind;
+
+        let files = (0..8)
+            .map(|i| temp_file(&format!("test_par_err{}", i), "text"))
+            .collect::<Vec<_>>();
+
+        // Opening a directory succeeds but reading it fails with a different error kind than
+        // opening a non-existing file, so the two failures can be told apart
+        let directory = env::temp_dir();
+        let non_existing = PathBuf::from("non_existing_file");
+
+        for (first, second, expected) in [
+            (&directory, &non_existing, ErrorKind::IsADirectory),
+            (&non_existing, &directory, ErrorKind::NotFound),
+        ] {
+            let mut paths = files.iter().map(|f| f.0.clone()).collect::<Vec<_>>();
+            paths.insert(2, first.clone());
+            paths.push(second.clone());
+
+            let sequential = read_files(&paths).expect_err("reading should fail");
+            let parallel = read_files_par(&paths).expect_err("reading should fail");
+            assert_eq!(sequential.kind(), expected);
+            assert_eq!(parallel.kind(), expected);
+        }
+    }
+
     #[rstest]
     fn build_non_linear_structure() {
         use std::collections::BinaryHeap;
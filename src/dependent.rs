@@ -15,7 +15,9 @@
 #![allow(clippy::len_without_is_empty)]
 #![allow(clippy::new_without_default)]
 
-use std::marker::PhantomData;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
 /// Trait representing a type-level definition of natural numbers (Peano numbers).
 pub trait Nat {
@@ -88,6 +90,103 @@ where
 {
 }
 
+/// Inductive predicate "`Self` is an even natural number".
+pub trait Even {}
+
+/// Inductive predicate "`Self` is an odd natural number".
+pub trait Odd {}
+
+/// Base case: [Zero] is even.
+impl Even for Zero {}
+
+/// Inductive step: `N + 1` is odd if `N` is even.
+impl<N: Nat + Even> Odd for Succ<N> {}
+
+/// Inductive step: `N + 1` is even if `N` is odd.
+impl<N: Nat + Odd> Even for Succ<N> {}
+
+/// Function which can only be called (i.e. compiles) with an [Even] natural number `N`.
+///
+/// Such bound can be used to statically reject e.g. odd-length [Vector]s.
+///
+/// # Example
+/// ```
+/// # use rust_examples::dependent::{require_even, Succ, Zero};
+/// require_even::<Zero>();
+/// require_even::<Succ<Succ<Zero>>>();
+/// ```
+///
+/// # 1 is not even
+/// ```compile_fail
+/// # use rust_examples::dependent::{require_even, Succ, Zero};
+/// require_even::<Succ<Zero>>();
+/// ```
+///
+/// # 3 is not even
+/// ```compile_fail
+/// # use rust_examples::dependent::{require_even, Succ, Zero};
+/// require_even::<Succ<Succ<Succ<Zero>>>>();
+/// ```
+pub fn require_even<N: Nat + Even>() {}
+
+/// Reflects a runtime (term-level) number `n` as an opaque object which yields `n` when called,
+/// just like [Nat::lower] does for a type-level number.
+///
+/// Note that the type-level information is lost here - all numbers have the same type
+/// `Box<dyn Fn() -> usize>`, so the compiler can't reason about `n` anymore. To get back to the
+/// type level, see [with_nat].
+pub fn reify(n: usize) -> Box<dyn Fn() -> usize> {
+    Box::new(move || n)
+}
+
+/// A *polymorphic function* over type-level natural numbers.
+///
+/// Rust closures can't be generic, so this trait plays the role of a continuation which can be
+/// called with any `N: Nat` (see [with_nat]).
+pub trait NatFn<R> {
+    /// Call this function with a type-level natural number `N`
+    fn call<N: Nat>(self) -> R;
+}
+
+macro_rules! nat_dispatch {
+    ($n:expr, $f:expr, $t:ty;) => {
+        None
+    };
+    ($n:expr, $f:expr, $t:ty; $i:literal $(, $rest:literal)*) => {
+        if $n == $i {
+            Some($f.call::<$t>())
+        } else {
+            nat_dispatch!($n, $f, Succ<$t>; $($rest),*)
+        }
+    };
+}
+
+/// Lifts a runtime number `n` to the type level and calls the continuation `f` with the
+/// corresponding type `N: Nat` (e.g. `Succ<Succ<Zero>>` for `n = 2`).
+///
+/// This is the *continuation-passing style (CPS)* entry point from terms to types. Since each
+/// type-level number is a distinct type, there must be a separate (monomorphized) branch for each
+/// `n`. These branches are generated by a macro and limited to small numbers `n <= 7`, for larger
+/// `n` the result is `None`.
+///
+/// # Example
+/// ```
+/// # use rust_examples::dependent::{with_nat, Nat, NatFn};
+/// struct Lower;
+///
+/// impl NatFn<usize> for Lower {
+///     fn call<N: Nat>(self) -> usize {
+///         N::lower()
+///     }
+/// }
+///
+/// assert_eq!(with_nat(3, Lower), Some(3));
+/// assert_eq!(with_nat(8, Lower), None);
+/// ```
+pub fn with_nat<R>(n: usize, f: impl NatFn<R>) -> Option<R> {
+    nat_dispatch!(n, f, Zero; 0, 1, 2, 3, 4, 5, 6, 7)
+}
+
 /// A wrapper for [`Vec<A>`](Vec) which preserves the information about its size `N` at the type
 /// level (i.e. compilation time).
 ///
@@ -112,6 +211,51 @@ impl<N: Nat, A> Vector<N, A> {
     pub fn len(&self) -> usize {
         Self::size()
     }
+
+    /// Lowers the compile-time length of this [Vector] into runtime, i.e. returns both the length
+    /// `N::lower()` and the name of the type `N` that carries it.
+    ///
+    /// This is mainly useful for debugging as it shows how the phantom [Nat] relates to the
+    /// stored data. Note that the type name is only meant to be human-readable, its exact format
+    /// is not guaranteed (see [`type_name`](core::any::type_name)).
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_examples::dependent::Vector;
+    /// let (size, proof) = Vector::new().cons(1).size_proof();
+    /// assert_eq!(1, size);
+    /// assert!(proof.contains("Succ") && proof.contains("Zero"));
+    /// ```
+    pub fn size_proof(&self) -> (usize, &'static str) {
+        debug_assert_eq!(
+            self.0.len(),
+            N::lower(),
+            "stored data must match the type-level length"
+        );
+        (N::lower(), core::any::type_name::<N>())
+    }
+
+    /// Lifts a runtime [Vec] into a [Vector] of length `N` if `v` has exactly `N` elements,
+    /// otherwise returns `None`.
+    ///
+    /// This is the only place where the length is checked at runtime, afterwards it's carried by
+    /// the type.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_examples::dependent::{Succ, Vector, Zero};
+    /// type Two = Succ<Succ<Zero>>;
+    ///
+    /// assert!(Vector::<Two, _>::try_from_vec(vec![1, 2]).is_some());
+    /// assert!(Vector::<Two, _>::try_from_vec(vec![1, 2, 3]).is_none());
+    /// ```
+    pub fn try_from_vec(v: Vec<A>) -> Option<Self> {
+        if v.len() == N::lower() {
+            Some(Self(v, PhantomData))
+        } else {
+            None
+        }
+    }
 }
 
 impl<N: Nat, A> Vector<N, A> {
@@ -124,8 +268,127 @@ impl<N: Nat, A> Vector<N, A> {
         xs.insert(0, x);
         Vector(xs, PhantomData)
     }
+
+    /// Add given element to the back of this [Vector].
+    ///
+    /// Besides the new vector, this returns a *witness* - a closure which lowers the old and new
+    /// length from the type level. The new length is `Succ<N>` by the signature of this method, so
+    /// the witness is a runtime evidence of the static proof that the length grew by exactly one.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_examples::dependent::Vector;
+    /// let (v, witness) = Vector::new().cons(1).append_one(2);
+    /// assert_eq!((1, 2), witness());
+    /// assert_eq!(2, v.len());
+    /// ```
+    pub fn append_one(self, x: A) -> (Vector<Succ<N>, A>, impl Fn() -> (usize, usize)) {
+        let mut xs = self.0;
+        xs.push(x);
+        (Vector(xs, PhantomData), || (N::lower(), Succ::<N>::lower()))
+    }
+
+    /// Get a reference to the element at index `i` without a bounds check.
+    ///
+    /// The index has already been checked once, when the [Fin] was constructed, and the fact that
+    /// it's a valid index into this [Vector] is now carried by the type.
+    pub fn get(&self, i: Fin<N>) -> &A {
+        // Safety: By the construction of `Fin<N>` it holds that `i < N` and a `Vector<N, _>`
+        // always contains exactly `N` elements.
+        unsafe { self.0.get_unchecked(i.get()) }
+    }
+}
+
+impl<N, A> Vector<Succ<N>, A>
+where
+    N: Nat + Pred<Succ<N>>, // N = Succ<N> - 1
+{
+    /// Remove the element from the front of this non-empty [Vector] and return it together with
+    /// the rest of the vector.
+    ///
+    /// The resulting vector has length `N` which is the predecessor of the original length
+    /// `Succ<N>`. Note that the precondition is encoded by the `Succ<N>` in the type of `self`,
+    /// not just by the [Pred] relation, because [Zero] is (by definition) a predecessor of itself.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_examples::dependent::Vector;
+    /// let (x, v) = Vector::new().cons(2).cons(1).pop();
+    /// assert_eq!(1, x);
+    /// assert_eq!(1, v.len());
+    /// ```
+    ///
+    /// Popping from an empty vector does not compile:
+    /// ```compile_fail
+    /// # use rust_examples::dependent::{Vector, Zero};
+    /// let v = Vector::<Zero, u8>::new();
+    /// v.pop();
+    /// ```
+    pub fn pop(self) -> (A, Vector<N, A>) {
+        let mut xs = self.0;
+        // A `Vector<Succ<N>, _>` contains at least one element, so this never panics
+        let x = xs.remove(0);
+        (x, Vector(xs, PhantomData))
+    }
+}
+
+/// Type representing a natural number strictly less than `N`, i.e. an element of the finite set
+/// `{0, 1, ..., N - 1}`.
+///
+/// [Fin] is the standard tool for bounds-checked indexing with dependent types. A value can only
+/// be constructed if it's within bounds, so any [`Fin<N>`](Fin) is a valid index into a
+/// [`Vector<N, _>`](Vector) (see [Vector::get]).
+///
+/// Note that there are no values of type [`Fin<Zero>`](Fin).
+pub struct Fin<N: Nat>(usize, PhantomData<N>);
+
+impl<N: Nat> Fin<N> {
+    /// Constructs new [Fin] only if `i < N`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_examples::dependent::{Fin, Succ, Zero};
+    /// assert!(Fin::<Succ<Zero>>::new(0).is_some());
+    /// assert!(Fin::<Succ<Zero>>::new(1).is_none());
+    /// ```
+    #[inline]
+    pub fn new(i: usize) -> Option<Self> {
+        if i < N::lower() {
+            Some(Self(i, PhantomData))
+        } else {
+            None
+        }
+    }
+
+    /// Lower this [Fin] to the term-level value it represents.
+    #[inline]
+    pub fn get(&self) -> usize {
+        self.0
+    }
+
+    /// Embeds `i < N` into `i + 1 < N + 1`.
+    #[inline]
+    pub fn succ(self) -> Fin<Succ<N>> {
+        Fin(self.0 + 1, PhantomData)
+    }
+}
+
+impl<N: Nat> Fin<Succ<N>> {
+    /// Zero is less than any successor `N + 1`, so this construction can't fail.
+    #[inline]
+    pub fn zero() -> Self {
+        Self(0, PhantomData)
+    }
 }
 
+impl<N: Nat> Clone for Fin<N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N: Nat> Copy for Fin<N> {}
+
 /// Trait representing a heterogeneous list, a.k.a [HList] of length `N`.
 ///
 /// Similarly to the simple example of [Vector], a `HList` also depends on its length `N`.
@@ -168,6 +431,25 @@ pub trait HList<N: Nat> {
     fn len(&self) -> usize {
         N::lower()
     }
+
+    /// Reverse the order of elements of this [HList] (see [HReverse]).
+    ///
+    /// The result is again an [HList] of the same length `N`, which is guaranteed by the bound on
+    /// the [`Output`](HReverse::Output) so that generic callers can rely on it.
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_examples::dependent::{HList, HNil, IntoTuple};
+    /// let hlist = HNil.cons(1).cons("two").cons(true);
+    /// assert_eq!((1, "two", true), hlist.reverse().into_tuple());
+    /// ```
+    fn reverse(self) -> <Self as HReverse>::Output
+    where
+        Self: Sized + HReverse,
+        <Self as HReverse>::Output: HList<N>,
+    {
+        self.reverse_onto(HNil)
+    }
 }
 
 /// Structure representing the null pointer at the end of each [HList].
@@ -209,6 +491,28 @@ impl<N: Nat, M: Nat + Pred<N>, H, T: HList<M>> HCons<N, M, H, T> {
     pub fn tail(&self) -> &T {
         &self.1
     }
+
+    /// Destructure this [HCons] into an owned head element and tail [HList].
+    #[inline]
+    pub fn into_head_tail(self) -> (H, T) {
+        (self.0, self.1)
+    }
+
+    /// Take an immutable reference to the element at the type-level index `I` (see [HIndex]).
+    ///
+    /// # Example
+    /// ```
+    /// # use rust_examples::dependent::{HList, HNil, Succ, Zero};
+    /// let hlist = HNil.cons(1).cons("two").cons(true);
+    /// assert_eq!(&"two", hlist.get::<Succ<Zero>>());
+    /// ```
+    #[inline]
+    pub fn get<I: Nat>(&self) -> &<Self as HIndex<I>>::Output
+    where
+        Self: HIndex<I>,
+    {
+        self.at()
+    }
 }
 
 /// [`HCons<N, M, _, T>`](HCons) is a `HList` of length `N > 0` if `T` is a `HList` of length
@@ -221,68 +525,325 @@ where
 {
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Trait which indexes an [HList] by a type-level index `I` (i.e. a [Nat]).
+///
+/// The element is found by walking the list `I` steps through the recursion on the type level and
+/// its type is resolved through the associated type [`Output`](Self::Output). There is no
+/// implementation for [HNil], so indexing past the end is a compilation error.
+///
+/// # Example
+/// ```compile_fail
+/// # use rust_examples::dependent::{HList, HNil, Succ, Zero};
+/// type Two = Succ<Succ<Zero>>;
+///
+/// let hlist = HNil.cons(1).cons("two");
+/// let _ = hlist.get::<Two>();
+/// ```
+pub trait HIndex<I: Nat> {
+    /// Type of the element at index `I`
+    type Output;
 
-    #[test]
-    fn trivial_nats() {
-        // Instances of 0 and 1 from the inductive definition of natural numbers (Peano numbers)
-        let _zero = Zero;
-        let _one = Succ::<Zero>;
+    /// Take an immutable reference to the element at index `I`.
+    fn at(&self) -> &Self::Output;
+}
+
+/// The element at index [Zero] of a [HCons] is its head.
+impl<N, M, H, T> HIndex<Zero> for HCons<N, M, H, T> {
+    type Output = H;
+
+    #[inline]
+    fn at(&self) -> &Self::Output {
+        &self.0
     }
+}
 
-    #[test]
-    fn nat_pred() {
-        fn pred<M, N>() -> usize
-        where
-            N: Nat,
-            M: Nat + Pred<N>,
-        {
-            M::lower()
-        }
+/// The element at index `I + 1` of a [HCons] is the element at index `I` of its tail.
+impl<N, M, H, T, I> HIndex<Succ<I>> for HCons<N, M, H, T>
+where
+    I: Nat,
+    T: HIndex<I>,
+{
+    type Output = T::Output;
 
-        assert_eq!(0, pred::<Zero, Zero>());
-        assert_eq!(0, pred::<Zero, Succ<Zero>>());
-        assert_eq!(1, pred::<Succ<Zero>, Succ<Succ<Zero>>>());
+    #[inline]
+    fn at(&self) -> &Self::Output {
+        self.1.at()
     }
+}
 
-    #[test]
-    fn add_nats() {
-        fn add<M, N, X>()
-        where
-            M: Nat + AddEq<N, X>, // M + N = X
-            N: Nat,
-            X: Nat,
-        {
+/// Trait which reverses an [HList] onto an accumulator `Acc` which is an [HList] of length `AccN`
+/// (i.e. the elements of `Self` are moved one by one to the front of `Acc`).
+///
+/// Since the elements have different types, the type of the result must be computed as well. This
+/// is done by the recursion on the type level through the associated type
+/// [`Output`](Self::Output). Starting with an empty accumulator (the defaults), the result is the
+/// reversed [HList] of the same length.
+pub trait HReverse<AccN = Zero, Acc = HNil> {
+    /// Type of the reversed [HList] (prepended to `Acc`)
+    type Output;
+
+    /// Prepend the elements of this [HList] to `acc` in reverse order.
+    fn reverse_onto(self, acc: Acc) -> Self::Output;
+}
+
+/// Reversing [HNil] onto an accumulator is the accumulator itself.
+impl<AccN, Acc> HReverse<AccN, Acc> for HNil {
+    type Output = Acc;
+
+    #[inline]
+    fn reverse_onto(self, acc: Acc) -> Self::Output {
+        acc
+    }
+}
+
+/// Reversing [HCons] moves the head to the front of the accumulator (increasing its length) and
+/// continues with the tail.
+impl<N, M, H, T, AccN, Acc> HReverse<AccN, Acc> for HCons<N, M, H, T>
+where
+    AccN: Nat,
+    T: HReverse<Succ<AccN>, HCons<Succ<AccN>, AccN, H, Acc>>,
+{
+    type Output = T::Output;
+
+    #[inline]
+    fn reverse_onto(self, acc: Acc) -> Self::Output {
+        let HCons(head, tail, _, _) = self;
+        tail.reverse_onto(HCons(head, acc, PhantomData, PhantomData))
+    }
+}
+
+/// Conversion of a (short) [HList] into an ordinary Rust tuple with the head as the first item.
+///
+/// Note that there's no variadic generics in Rust, so this is only implemented for lengths 1, 2
+/// and 3.
+///
+/// # Example
+/// ```
+/// # use rust_examples::dependent::{HList, HNil, IntoTuple};
+/// let hlist = HNil.cons(1).cons("two").cons(true);
+/// assert_eq!((true, "two", 1), hlist.into_tuple());
+/// ```
+pub trait IntoTuple {
+    /// Tuple type with the same elements as this [HList]
+    type Tuple;
+
+    /// Converts this [HList] into [`Tuple`](Self::Tuple)
+    fn into_tuple(self) -> Self::Tuple;
+}
+
+type One = Succ<Zero>;
+type Two = Succ<One>;
+type Three = Succ<Two>;
+
+impl<A> IntoTuple for HCons<One, Zero, A, HNil> {
+    type Tuple = (A,);
+
+    #[inline]
+    fn into_tuple(self) -> Self::Tuple {
+        (self.0,)
+    }
+}
+
+impl<A, B> IntoTuple for HCons<Two, One, A, HCons<One, Zero, B, HNil>> {
+    type Tuple = (A, B);
+
+    #[inline]
+    fn into_tuple(self) -> Self::Tuple {
+        let (a, tail) = self.into_head_tail();
+        let (b,) = tail.into_tuple();
+        (a, b)
+    }
+}
+
+impl<A, B, C> IntoTuple for HCons<Three, Two, A, HCons<Two, One, B, HCons<One, Zero, C, HNil>>> {
+    type Tuple = (A, B, C);
+
+    #[inline]
+    fn into_tuple(self) -> Self::Tuple {
+        let (a, tail) = self.into_head_tail();
+        let (b, c) = tail.into_tuple();
+        (a, b, c)
+    }
+}
+
+/// Conversion of a homogeneous collection into an [HList] of the same length.
+///
+/// This is implemented for arrays `[T; N]` which yield an [HList] with `N` cells of the same type
+/// `T` whose length [`Len`](Self::Len) is the Peano encoding of `N` (e.g. `Succ<Succ<Zero>>` for
+/// `N = 2`). Even though all the cells have the same type, each one keeps its own type slot, so
+/// the result can be further extended with elements of other types.
+///
+/// Const generics can't express `N - 1` (yet), so the implementations are generated by a macro
+/// (each using the one for the tail `[T; N - 1]`) and limited to arrays of length `N <= 8`.
+///
+/// # Example
+/// ```
+/// # use rust_examples::dependent::{HList, IntoHList, IntoTuple};
+/// let hlist = [1, 2, 3].into_hlist();
+/// assert_eq!(3, hlist.len());
+/// assert_eq!((1, 2, 3), hlist.into_tuple());
+/// ```
+pub trait IntoHList {
+    /// Type-level length of the resulting [HList]
+    type Len: Nat;
+
+    /// The resulting [HList] of length [`Len`](Self::Len)
+    type Output: HList<Self::Len>;
+
+    /// Converts `self` into an [HList] of type [`Output`](Self::Output)
+    fn into_hlist(self) -> Self::Output;
+}
+
+/// Empty array is converted to [HNil].
+impl<T> IntoHList for [T; 0] {
+    type Len = Zero;
+    type Output = HNil;
+
+    #[inline]
+    fn into_hlist(self) -> Self::Output {
+        HNil
+    }
+}
+
+macro_rules! array_into_hlist {
+    ($prev:literal;) => {};
+    ($prev:literal; $n:literal $(, $rest:literal)*) => {
+        impl<T> IntoHList for [T; $n] {
+            type Len = Succ<<[T; $prev] as IntoHList>::Len>;
+            type Output = HCons<
+                Self::Len,
+                <[T; $prev] as IntoHList>::Len,
+                T,
+                <[T; $prev] as IntoHList>::Output,
+            >;
+
+            #[inline]
+            fn into_hlist(self) -> Self::Output {
+                let [head, tail @ ..] = self;
+                HCons::new(head, tail.into_hlist())
+            }
         }
 
-        // 0 + 0 = 0
-        add::<Zero, Zero, Zero>();
-        // 0 + 1 = 1
-        add::<Zero, Succ<Zero>, Succ<Zero>>();
-        // 1 + 0 = 1
-        add::<Succ<Zero>, Zero, Succ<Zero>>();
-        // 1 + 1 = 2
-        add::<Succ<Zero>, Succ<Zero>, Succ<Succ<Zero>>>();
-        // 2 + 1 = 3
-        add::<Succ<Succ<Zero>>, Succ<Zero>, Succ<Succ<Succ<Zero>>>>();
+        array_into_hlist!($n; $($rest),*);
+    };
+}
+
+array_into_hlist!(0; 1, 2, 3, 4, 5, 6, 7, 8);
+
+/// Trait for [HList]s which can be zipped with an `Other` [HList] of the same length into an
+/// [HList] of pairs of corresponding elements.
+///
+/// The lengths must match at the type level - [HNil] can only be zipped with [HNil] and an
+/// [`HCons<N, M, _, _>`](HCons) only with another [`HCons<N, M, _, _>`](HCons).
+///
+/// # Example
+/// ```
+/// # use rust_examples::dependent::{HList, HNil, HZip, IntoTuple};
+/// let xs = HNil.cons("two").cons(1);
+/// let ys = HNil.cons(2.0).cons(true);
+/// assert_eq!(((1, true), ("two", 2.0)), xs.zip(ys).into_tuple());
+/// ```
+///
+/// Zipping [HList]s of different lengths does not compile:
+/// ```compile_fail
+/// # use rust_examples::dependent::{HList, HNil, HZip};
+/// let xs = HNil.cons("two").cons(1);
+/// let ys = HNil.cons(true);
+/// xs.zip(ys);
+/// ```
+pub trait HZip<Other> {
+    /// [HList] of pairs of elements from `Self` and `Other`
+    type Zipped;
+
+    /// Pair corresponding elements of this and `other` [HList].
+    fn zip(self, other: Other) -> Self::Zipped;
+}
+
+/// Zipping two empty [HList]s yields an empty [HList].
+impl HZip<HNil> for HNil {
+    type Zipped = HNil;
+
+    #[inline]
+    fn zip(self, _other: HNil) -> Self::Zipped {
+        HNil
     }
+}
 
-    #[test]
-    fn sized_vec() {
-        let v = Vector::<Zero, u8>::new();
-        let v_prime = v.cons(42);
-        assert_eq!(1, v_prime.len());
+/// Zipping two [HCons] of the same length `N` pairs the heads and zips the tails (of length `M`).
+impl<N, M, H1, T1, H2, T2> HZip<HCons<N, M, H2, T2>> for HCons<N, M, H1, T1>
+where
+    T1: HZip<T2>,
+{
+    type Zipped = HCons<N, M, (H1, H2), T1::Zipped>;
+
+    #[inline]
+    fn zip(self, other: HCons<N, M, H2, T2>) -> Self::Zipped {
+        let HCons(h1, t1, _, _) = self;
+        let HCons(h2, t2, _, _) = other;
+        HCons((h1, h2), t1.zip(t2), PhantomData, PhantomData)
     }
+}
 
-    #[test]
-    fn make_hlist() {
-        let hlist = HNil;
-        assert_eq!(0, hlist.len());
+/// A *polymorphic function* which folds an element of type `E` into an accumulator of type `Acc`.
+///
+/// Since elements of a [HList] have different types, a folding function can't be an ordinary
+/// closure. Instead, it is a type which implements [Folder] for each element type it can handle
+/// (in the spirit of [frunk](https://crates.io/crates/frunk)).
+pub trait Folder<Acc, E> {
+    /// Combine current accumulator `acc` with given element `elem`
+    fn apply(&self, acc: Acc, elem: E) -> Acc;
+}
 
-        let hlist = hlist.cons(1).cons("two").cons(true);
-        assert_eq!(3, hlist.len());
+/// Trait for [HList]s which can be folded from left to right by a [Folder] `F`.
+///
+/// # Example
+/// ```
+/// # use rust_examples::dependent::{Folder, HFold, HList, HNil};
+/// struct Count;
+///
+/// impl<E> Folder<usize, E> for Count {
+///     fn apply(&self, acc: usize, _elem: E) -> usize {
+///         acc + 1
+///     }
+/// }
+///
+/// let hlist = HNil.cons(1).cons("two").cons(true);
+/// assert_eq!(3, hlist.fold(0, &Count));
+/// ```
+pub trait HFold<Acc, F> {
+    /// Thread the accumulator `acc` through all elements of this [HList] using the folder `f`.
+    fn fold(self, acc: Acc, f: &F) -> Acc;
+}
+
+/// Folding an empty [HList] is the identity on the accumulator.
+impl<Acc, F> HFold<Acc, F> for HNil {
+    #[inline]
+    fn fold(self, acc: Acc, _f: &F) -> Acc {
+        acc
+    }
+}
+
+/// Folding [HCons] applies the folder to the head and continues with the tail. Note that this
+/// requires `F` to be a [Folder] for the type of each element in the list.
+impl<N, M, H, T, Acc, F> HFold<Acc, F> for HCons<N, M, H, T>
+where
+    F: Folder<Acc, H>,
+    T: HFold<Acc, F>,
+{
+    #[inline]
+    fn fold(self, acc: Acc, f: &F) -> Acc {
+        let HCons(head, tail, _, _) = self;
+        tail.fold(f.apply(acc, head), f)
+    }
+}
+
+/// [Folder] which sums up all elements that can be converted [`Into<f64>`](Into).
+pub struct SumFolder;
+
+impl<E: Into<f64>> Folder<f64, E> for SumFolder {
+    #[inline]
+    fn apply(&self, acc: f64, elem: E) -> f64 {
+        acc + elem.into()
     }
 }
 
@@ -349,3 +910,277 @@ impl NotAddTest {
     {
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trivial_nats() {
+        // Instances of 0 and 1 from the inductive definition of natural numbers (Peano numbers)
+        let _zero = Zero;
+        let _one = Succ::<Zero>;
+    }
+
+    #[test]
+    fn nat_pred() {
+        fn pred<M, N>() -> usize
+        where
+            N: Nat,
+            M: Nat + Pred<N>,
+        {
+            M::lower()
+        }
+
+        assert_eq!(0, pred::<Zero, Zero>());
+        assert_eq!(0, pred::<Zero, Succ<Zero>>());
+        assert_eq!(1, pred::<Succ<Zero>, Succ<Succ<Zero>>>());
+    }
+
+    #[test]
+    fn add_nats() {
+        fn add<M, N, X>()
+        where
+            M: Nat + AddEq<N, X>, // M + N = X
+            N: Nat,
+            X: Nat,
+        {
+        }
+
+        // 0 + 0 = 0
+        add::<Zero, Zero, Zero>();
+        // 0 + 1 = 1
+        add::<Zero, Succ<Zero>, Succ<Zero>>();
+        // 1 + 0 = 1
+        add::<Succ<Zero>, Zero, Succ<Zero>>();
+        // 1 + 1 = 2
+        add::<Succ<Zero>, Succ<Zero>, Succ<Succ<Zero>>>();
+        // 2 + 1 = 3
+        add::<Succ<Succ<Zero>>, Succ<Zero>, Succ<Succ<Succ<Zero>>>>();
+    }
+
+    #[test]
+    fn even_nats() {
+        fn even_len<N: Nat + Even, A>(v: &Vector<N, A>) -> usize {
+            require_even::<N>();
+            v.len()
+        }
+
+        fn odd<N: Nat + Odd>() -> usize {
+            N::lower()
+        }
+
+        require_even::<Zero>();
+        require_even::<Succ<Succ<Zero>>>();
+        assert_eq!(1, odd::<Succ<Zero>>());
+        assert_eq!(3, odd::<Succ<Succ<Succ<Zero>>>>());
+
+        let v = Vector::<Zero, u8>::new().cons(1).cons(2);
+        assert_eq!(2, even_len(&v));
+    }
+
+    #[test]
+    fn reify_nat() {
+        assert_eq!(3, reify(3)());
+        assert_eq!(Succ::<Zero>::lower(), reify(1)());
+    }
+
+    #[test]
+    fn lift_nat() {
+        struct TypeName;
+
+        impl NatFn<&'static str> for TypeName {
+            fn call<N: Nat>(self) -> &'static str {
+                std::any::type_name::<N>()
+            }
+        }
+
+        assert_eq!(with_nat(0, TypeName), Some(std::any::type_name::<Zero>()));
+        assert_eq!(
+            with_nat(3, TypeName),
+            Some(std::any::type_name::<Succ<Succ<Succ<Zero>>>>())
+        );
+        assert_eq!(with_nat(42, TypeName), None);
+    }
+
+    #[test]
+    fn sized_vec() {
+        let v = Vector::<Zero, u8>::new();
+        let v_prime = v.cons(42);
+        assert_eq!(1, v_prime.len());
+    }
+
+    #[test]
+    fn vec_into_sized_vec() {
+        type Two = Succ<Succ<Zero>>;
+
+        let v = Vector::<Two, _>::try_from_vec(vec![1, 2]).expect("vec of length 2");
+        assert_eq!(2, v.len());
+        assert_eq!(&1, v.get(Fin::zero()));
+        assert_eq!(&2, v.get(Fin::zero().succ()));
+
+        assert!(Vector::<Two, _>::try_from_vec(vec![1, 2, 3]).is_none());
+        assert!(Vector::<Two, u8>::try_from_vec(vec![]).is_none());
+        assert!(Vector::<Zero, u8>::try_from_vec(vec![]).is_some());
+    }
+
+    #[test]
+    fn size_proof() {
+        let v = Vector::<Zero, u8>::new().cons(1).cons(2);
+        let (size, proof) = v.size_proof();
+
+        assert_eq!(v.len(), size);
+        assert_eq!(2, proof.matches("Succ").count());
+        assert_eq!(1, proof.matches("Zero").count());
+    }
+
+    #[test]
+    fn pop_front() {
+        let v = Vector::<Zero, u8>::new().cons(1).cons(2);
+        assert_eq!(2, v.len());
+
+        let (x, v) = v.pop();
+        assert_eq!(2, x);
+        assert_eq!(1, v.len());
+        assert_eq!(&1, v.get(Fin::zero()));
+
+        let (x, v) = v.pop();
+        assert_eq!(1, x);
+        assert_eq!(0, v.len());
+    }
+
+    #[test]
+    fn append_with_witness() {
+        let v = Vector::<Zero, u8>::new().cons(1).cons(2);
+
+        let (v, witness) = v.append_one(3);
+        let (old_len, new_len) = witness();
+        assert_eq!(old_len + 1, new_len);
+        assert_eq!(new_len, v.len());
+
+        // The element was appended to the back
+        assert_eq!(&2, v.get(Fin::zero()));
+        assert_eq!(&3, v.get(Fin::zero().succ().succ()));
+    }
+
+    #[test]
+    fn fin_bounds() {
+        type Two = Succ<Succ<Zero>>;
+
+        assert_eq!(Some(0), Fin::<Two>::new(0).map(|i| i.get()));
+        assert_eq!(Some(1), Fin::<Two>::new(1).map(|i| i.get()));
+        assert!(Fin::<Two>::new(2).is_none());
+        assert!(Fin::<Zero>::new(0).is_none());
+
+        assert_eq!(0, Fin::<Two>::zero().get());
+        assert_eq!(1, Fin::<Succ<Zero>>::zero().succ().get());
+    }
+
+    #[test]
+    fn safe_vec_indexing() {
+        let v = Vector::<Zero, u8>::new().cons(1).cons(2);
+
+        assert_eq!(&2, v.get(Fin::zero()));
+        assert_eq!(&1, v.get(Fin::zero().succ()));
+
+        let i = Fin::new(1).expect("index within bounds");
+        assert_eq!(&1, v.get(i));
+    }
+
+    #[test]
+    fn make_hlist() {
+        let hlist = HNil;
+        assert_eq!(0, hlist.len());
+
+        let hlist = hlist.cons(1).cons("two").cons(true);
+        assert_eq!(3, hlist.len());
+    }
+
+    #[test]
+    fn hlist_tuple_round_trip() {
+        let hlist = HNil.cons(1).cons("two").cons(true);
+
+        let (head, tail) = hlist.into_head_tail();
+        assert!(head);
+        assert_eq!(2, tail.len());
+
+        let (a, b, c) = tail.cons(head).into_tuple();
+        assert_eq!((a, b, c), (true, "two", 1));
+
+        let hlist = HNil.cons(c).cons(b).cons(a);
+        assert_eq!(3, hlist.len());
+        assert_eq!((true, "two", 1), hlist.into_tuple());
+    }
+
+    #[test]
+    fn hlist_index() {
+        let hlist = HNil.cons(1).cons("two").cons(true);
+
+        assert!(*hlist.get::<Zero>());
+        assert_eq!(&"two", hlist.get::<One>());
+        assert_eq!(&1, hlist.get::<Two>());
+    }
+
+    #[test]
+    fn fold_hlist() {
+        let hlist = HNil.cons(1i32).cons(2.0f64);
+        assert_eq!(3.0, hlist.fold(0.0, &SumFolder));
+        assert_eq!(4.2, HNil.fold(4.2, &SumFolder));
+    }
+
+    #[test]
+    fn zip_hlists() {
+        let xs = HNil.cons("a").cons(1);
+        let ys = HNil.cons(2.0).cons(true);
+
+        let zipped = xs.zip(ys);
+        assert_eq!(2, zipped.len());
+        assert_eq!(((1, true), ("a", 2.0)), zipped.into_tuple());
+
+        assert_eq!(0, HNil.zip(HNil).len());
+    }
+
+    #[test]
+    fn reverse_hlist() {
+        let hlist = HNil.cons(1).cons("two").cons(true);
+        assert_eq!(&true, hlist.head());
+
+        let reversed = hlist.reverse();
+        assert_eq!(3, reversed.len());
+        assert_eq!(&1, reversed.head());
+        assert_eq!(&"two", reversed.tail().head());
+        assert_eq!(&true, reversed.tail().tail().head());
+
+        assert_eq!(0, HNil.reverse().len());
+    }
+
+    #[test]
+    fn reverse_hlist_generic() {
+        // Generic code can rely on the reversed list being an `HList` of the same length
+        fn reversed_len<N: Nat, L: HList<N> + HReverse>(hlist: L) -> usize
+        where
+            L::Output: HList<N>,
+        {
+            HList::<N>::len(&hlist.reverse())
+        }
+
+        assert_eq!(3, reversed_len(HNil.cons(1).cons("two").cons(true)));
+        assert_eq!(0, reversed_len(HNil));
+    }
+
+    #[test]
+    fn array_into_hlist() {
+        let hlist = [1, 2, 3].into_hlist();
+        assert_eq!(3, hlist.len());
+        assert_eq!(&1, hlist.head());
+        assert_eq!(&2, hlist.tail().head());
+        assert_eq!(&3, hlist.tail().tail().head());
+
+        // each cell keeps its own type slot, so the result can be extended with other types
+        let hlist = hlist.cons("four");
+        assert_eq!(4, hlist.len());
+
+        assert_eq!(8, [0u8; 8].into_hlist().len());
+        assert_eq!(0, ([] as [i32; 0]).into_hlist().len());
+    }
+}
@@ -10,6 +10,7 @@
 //! encapsulates common behavior of types in the class of differentiable functions and makes both
 //! GD functions mentioned above polymorphic over this behavior.
 use std::boxed::Box;
+use std::marker::PhantomData;
 
 /// Interface of a real 1D differentiable function
 pub trait Differentiable {
@@ -17,7 +18,8 @@ pub trait Differentiable {
     fn grad(&self, x: f64) -> f64;
 }
 
-#[allow(dead_code)]
+/// Quadratic function `f(x) = a*x^2 - b*x + c`
+#[derive(Debug, PartialEq)]
 pub struct Quadratic {
     a: f64,
     b: f64,
@@ -36,6 +38,105 @@ impl Quadratic {
     }
 }
 
+/// Marker type of a [QuadraticBuilder] coefficient which has not been set yet
+pub struct Unset;
+
+/// Marker type of a [QuadraticBuilder] coefficient which has already been set
+pub struct Set;
+
+/// Builder of a [Quadratic] function which tracks which coefficients have been set at the type
+/// level.
+///
+/// This is the so called *type-state* pattern. Each of the type parameters `A`, `B` and `C` is
+/// either [Unset] or [Set] and each `with_*` method, which is only available while the
+/// corresponding coefficient is [Unset], changes it to [Set]. So setting a coefficient twice is a
+/// compilation error and so is forgetting to set any of them, because the
+/// [`build`](QuadraticBuilder::build) method is only implemented for `QuadraticBuilder<Set, Set,
+/// Set>`. The markers are just [PhantomData], so the builder has the same size as [Quadratic].
+///
+/// # Example
+/// ```
+/// use rust_examples::dispatch::{Integrable, Quadratic};
+///
+/// let f = Quadratic::builder().with_a(1.).with_c(3.).with_b(2.).build();
+/// assert_eq!(f.eval(1.), 2.);
+/// ```
+///
+/// Calling `build` before setting all the coefficients does not compile:
+/// ```compile_fail
+/// use rust_examples::dispatch::Quadratic;
+///
+/// let f = Quadratic::builder().with_a(1.).with_b(2.).build();
+/// ```
+///
+/// Setting the same coefficient twice does not compile either:
+/// ```compile_fail
+/// use rust_examples::dispatch::Quadratic;
+///
+/// let f = Quadratic::builder().with_a(1.).with_b(2.).with_c(3.).with_a(4.).build();
+/// ```
+pub struct QuadraticBuilder<A, B, C> {
+    a: f64,
+    b: f64,
+    c: f64,
+    _state: PhantomData<(A, B, C)>,
+}
+
+impl Quadratic {
+    /// Creates new [QuadraticBuilder] with all the coefficients [Unset]
+    pub fn builder() -> QuadraticBuilder<Unset, Unset, Unset> {
+        QuadraticBuilder {
+            a: 0.,
+            b: 0.,
+            c: 0.,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<B, C> QuadraticBuilder<Unset, B, C> {
+    /// Sets the quadratic coefficient `a`
+    pub fn with_a(self, a: f64) -> QuadraticBuilder<Set, B, C> {
+        QuadraticBuilder {
+            a,
+            b: self.b,
+            c: self.c,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<A, C> QuadraticBuilder<A, Unset, C> {
+    /// Sets the linear coefficient `b`
+    pub fn with_b(self, b: f64) -> QuadraticBuilder<A, Set, C> {
+        QuadraticBuilder {
+            a: self.a,
+            b,
+            c: self.c,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<A, B> QuadraticBuilder<A, B, Unset> {
+    /// Sets the constant coefficient `c`
+    pub fn with_c(self, c: f64) -> QuadraticBuilder<A, B, Set> {
+        QuadraticBuilder {
+            a: self.a,
+            b: self.b,
+            c,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl QuadraticBuilder<Set, Set, Set> {
+    /// Builds the [Quadratic] function, which is only possible once all the coefficients are [Set]
+    pub fn build(self) -> Quadratic {
+        Quadratic::stack_alloc(self.a, self.b, self.c)
+    }
+}
+
 impl Differentiable for Quadratic {
     #[inline(always)]
     fn grad(&self, x: f64) -> f64 {
@@ -58,6 +159,72 @@ impl Differentiable for Trigonometric {
     }
 }
 
+/// Interface of a real 1D function which is differentiable twice
+pub trait TwiceDifferentiable: Differentiable {
+    /// Compute the second derivative of this function at given point `x`
+    fn grad2(&self, x: f64) -> f64;
+}
+
+impl TwiceDifferentiable for Quadratic {
+    #[inline(always)]
+    fn grad2(&self, _x: f64) -> f64 {
+        2. * self.a
+    }
+}
+
+impl TwiceDifferentiable for Trigonometric {
+    #[inline(always)]
+    fn grad2(&self, x: f64) -> f64 {
+        match self {
+            Trigonometric::Sine => -x.sin(),
+            Trigonometric::Cosine => -x.cos(),
+        }
+    }
+}
+
+/// Interface of a real 1D function which can be evaluated (and thus numerically integrated)
+pub trait Integrable {
+    /// Evaluate this function at given point `x`
+    fn eval(&self, x: f64) -> f64;
+}
+
+impl Integrable for Quadratic {
+    #[inline(always)]
+    fn eval(&self, x: f64) -> f64 {
+        self.a * x * x - self.b * x + self.c
+    }
+}
+
+impl Integrable for Trigonometric {
+    #[inline(always)]
+    fn eval(&self, x: f64) -> f64 {
+        match self {
+            Trigonometric::Sine => x.sin(),
+            Trigonometric::Cosine => x.cos(),
+        }
+    }
+}
+
+/// Numerically integrates `f` over the interval `[a, b]` using the
+/// [trapezoidal rule](https://en.wikipedia.org/wiki/Trapezoidal_rule) with `steps` sub-intervals.
+///
+/// Note that the integration uses dynamic dispatch on `f` (see [gradient_descent_dynamic]).
+///
+/// # Example
+/// ```
+/// use rust_examples::dispatch::{integrate, Quadratic};
+///
+/// // Integral of x^2 over [0, 3] is 9
+/// let f = Quadratic::stack_alloc(1., 0., 0.);
+/// assert!((integrate(&f, 0., 3., 1_000) - 9.).abs() < 1e-4);
+/// ```
+pub fn integrate(f: &dyn Integrable, a: f64, b: f64, steps: usize) -> f64 {
+    let steps = steps.max(1);
+    let h = (b - a) / steps as f64;
+    let inner = (1..steps).map(|i| f.eval(a + i as f64 * h)).sum::<f64>();
+    h * ((f.eval(a) + f.eval(b)) / 2. + inner)
+}
+
 /// Gradient Descent that finds a minimum of a statically defined function `f` on given `interval`.
 ///
 /// Static dispatch means that this function i *monomorphized* and thus the type of `f` is known at
@@ -99,10 +266,392 @@ pub fn gradient_descent_dynamic(f: &dyn Differentiable, max_iters: usize, eta: f
     x
 }
 
+/// Gradient Descent with *momentum* which accumulates an exponentially decaying moving average of
+/// past gradients (with decay rate `beta`) and moves in its direction.
+pub fn gradient_descent_momentum<F>(f: &F, max_iters: usize, eta: f64, beta: f64) -> f64
+where
+    F: Differentiable,
+{
+    let (mut x, mut v) = (0.0, 0.0);
+    for _ in 0..max_iters {
+        v = beta * v + f.grad(x);
+        x -= eta * v;
+    }
+    x
+}
+
+/// [Adam](https://arxiv.org/abs/1412.6980) optimizer which adapts the step size using
+/// exponentially decaying moving averages of past gradients (with decay rate `beta1`) and their
+/// squares (with decay rate `beta2`).
+///
+/// Both averages are initialized to zero and thus biased towards it in early iterations, which is
+/// corrected by dividing them by `1 - beta^t` in iteration `t`. Small `eps` prevents a division by
+/// zero.
+pub fn adam<F>(f: &F, max_iters: usize, eta: f64, beta1: f64, beta2: f64, eps: f64) -> f64
+where
+    F: Differentiable,
+{
+    let (mut x, mut m, mut v) = (0.0, 0.0, 0.0);
+    let (mut beta1_t, mut beta2_t) = (1.0, 1.0);
+    for _ in 0..max_iters {
+        let grad = f.grad(x);
+        m = beta1 * m + (1. - beta1) * grad;
+        v = beta2 * v + (1. - beta2) * grad * grad;
+
+        // Bias correction: `beta1_t = beta1^t` and `beta2_t = beta2^t`
+        beta1_t *= beta1;
+        beta2_t *= beta2;
+        let m_hat = m / (1. - beta1_t);
+        let v_hat = v / (1. - beta2_t);
+
+        x -= eta * m_hat / (v_hat.sqrt() + eps);
+    }
+    x
+}
+
+/// Newton's method that finds a stationary point of `f` using the second derivative (curvature)
+/// instead of a fixed step size.
+///
+/// Note that the method can't proceed at a point where the curvature vanishes, in which case the
+/// current point is returned.
+pub fn newton_method<F>(f: &F, max_iters: usize) -> f64
+where
+    F: TwiceDifferentiable,
+{
+    let mut x = 0.0;
+    for _ in 0..max_iters {
+        let curvature = f.grad2(x);
+        if curvature == 0. {
+            break;
+        }
+        x -= f.grad(x) / curvature;
+    }
+    x
+}
+
+/// Same as [gradient_descent_static] but returns the whole *trace* of visited points (starting
+/// with the initial point) instead of just the last one.
+pub fn gradient_descent_trace<F>(f: &F, max_iters: usize, eta: f64) -> Vec<f64>
+where
+    F: Differentiable,
+{
+    let mut trace = Vec::with_capacity(max_iters + 1);
+    let mut x = 0.0;
+    trace.push(x);
+    for _ in 0..max_iters {
+        x -= eta * f.grad(x);
+        trace.push(x);
+    }
+    trace
+}
+
+/// Same as [newton_method] but returns the whole *trace* of visited points (starting with the
+/// initial point) instead of just the last one.
+pub fn newton_trace<F>(f: &F, max_iters: usize) -> Vec<f64>
+where
+    F: TwiceDifferentiable,
+{
+    let mut trace = Vec::with_capacity(max_iters + 1);
+    let mut x = 0.0;
+    trace.push(x);
+    for _ in 0..max_iters {
+        let curvature = f.grad2(x);
+        if curvature == 0. {
+            break;
+        }
+        x -= f.grad(x) / curvature;
+        trace.push(x);
+    }
+    trace
+}
+
+/// Estimates the observed order of convergence `q` of an optimization `trace` towards a known
+/// `optimum`.
+///
+/// With errors `e_k = |x_k - optimum|`, an iterative method converges with order `q` if
+/// `e_{k+1} ~ C * e_k^q`, so `q` can be estimated from three successive errors as
+/// `ln(e_{k+1} / e_k) / ln(e_k / e_{k-1})`. This returns the last such estimate in the trace, so
+/// `1` means *linear* (e.g. fixed-step gradient descent) and `2` *quadratic* convergence (e.g.
+/// Newton's method).
+///
+/// If the trace hits the `optimum` exactly after a non-zero error, the convergence is considered
+/// to be infinitely fast and [f64::INFINITY] is returned. If there is not enough information in
+/// the trace to estimate the rate, the result is [f64::NAN].
+///
+/// # Example
+/// ```
+/// use rust_examples::dispatch::{convergence_rate, gradient_descent_trace, Quadratic};
+///
+/// // min { 2*x^2 - x } = -1/8 at x = 1/4
+/// let f = Quadratic::stack_alloc(2., 1., 0.);
+/// let trace = gradient_descent_trace(&f, 20, 0.01);
+/// assert!((convergence_rate(&trace, 0.25) - 1.).abs() < 1e-6);
+/// ```
+pub fn convergence_rate(trace: &[f64], optimum: f64) -> f64 {
+    let errors = trace
+        .iter()
+        .map(|x| (x - optimum).abs())
+        .collect::<Vec<_>>();
+
+    if let Some(hit) = errors.iter().position(|&e| e == 0.) {
+        return if hit > 0 { f64::INFINITY } else { f64::NAN };
+    }
+
+    errors
+        .windows(3)
+        .rev()
+        .map(|e| (e[2] / e[1]).ln() / (e[1] / e[0]).ln())
+        .find(|q| q.is_finite())
+        .unwrap_or(f64::NAN)
+}
+
+/// Renders an optimization `trace` (e.g. from [gradient_descent_trace]) as CSV with a header and
+/// rows `iteration,x`, so that it can be easily plotted outside of Rust.
+///
+/// # Example
+/// ```
+/// use rust_examples::dispatch::trace_to_csv;
+///
+/// assert_eq!(trace_to_csv(&[0., 0.5]), "iteration,x\n0,0\n1,0.5\n");
+/// ```
+pub fn trace_to_csv(trace: &[f64]) -> String {
+    trace
+        .iter()
+        .enumerate()
+        .fold(String::from("iteration,x\n"), |mut csv, (i, x)| {
+            csv.push_str(&format!("{},{}\n", i, x));
+            csv
+        })
+}
+
+/// Same as [trace_to_csv] but each row additionally contains the objective value `f(x)`, i.e. the
+/// rows are `iteration,x,value`.
+pub fn trace_with_values_to_csv(f: &dyn Integrable, trace: &[f64]) -> String {
+    trace
+        .iter()
+        .enumerate()
+        .fold(String::from("iteration,x,value\n"), |mut csv, (i, &x)| {
+            csv.push_str(&format!("{},{},{}\n", i, x, f.eval(x)));
+            csv
+        })
+}
+
+/// Optimization strategy used by [minimize] together with its hyper-parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Strategy {
+    /// Plain gradient descent with step size `eta`, see [gradient_descent_static]
+    Vanilla { eta: f64 },
+    /// Gradient descent with momentum, see [gradient_descent_momentum]
+    Momentum { eta: f64, beta: f64 },
+    /// Newton's method, see [newton_method]
+    Newton,
+    /// Adam optimizer, see [adam]
+    Adam {
+        eta: f64,
+        beta1: f64,
+        beta2: f64,
+        eps: f64,
+    },
+}
+
+/// Single entry point which minimizes `f` with given [Strategy].
+///
+/// The choice of an algorithm is a runtime value, yet each branch below is still statically
+/// dispatched on `f`. This is an example of selecting behavior by matching on an `enum` rather
+/// than via a `dyn` trait object.
+///
+/// # Example
+/// ```
+/// use rust_examples::dispatch::{minimize, Quadratic, Strategy};
+///
+/// // min { 2*x^2 - x } = -1/8 at x = 1/4
+/// let f = Quadratic::stack_alloc(2., 1., 0.);
+/// let x_min = minimize(&f, Strategy::Newton, 1);
+/// assert_eq!(x_min, 0.25);
+/// ```
+pub fn minimize(f: &impl TwiceDifferentiable, strategy: Strategy, max_iters: usize) -> f64 {
+    match strategy {
+        Strategy::Vanilla { eta } => gradient_descent_static(f, max_iters, eta),
+        Strategy::Momentum { eta, beta } => gradient_descent_momentum(f, max_iters, eta, beta),
+        Strategy::Newton => newton_method(f, max_iters),
+        Strategy::Adam {
+            eta,
+            beta1,
+            beta2,
+            eps,
+        } => adam(f, max_iters, eta, beta1, beta2, eps),
+    }
+}
+
+/// Macro that generates an `enum` with a variant for each given [Differentiable] type together
+/// with [Differentiable] and [TwiceDifferentiable] implementations which `match` on the variant
+/// and forward the call (see [AnyDifferentiable]).
+macro_rules! differentiable_enum {
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $($variant:ident($t:ty)),+ $(,)? }) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant($t)),+
+        }
+
+        impl Differentiable for $name {
+            #[inline(always)]
+            fn grad(&self, x: f64) -> f64 {
+                match self {
+                    $(Self::$variant(f) => f.grad(x)),+
+                }
+            }
+        }
+
+        impl TwiceDifferentiable for $name {
+            #[inline(always)]
+            fn grad2(&self, x: f64) -> f64 {
+                match self {
+                    $(Self::$variant(f) => f.grad2(x)),+
+                }
+            }
+        }
+
+        $(
+            impl From<$t> for $name {
+                #[inline(always)]
+                fn from(f: $t) -> Self {
+                    Self::$variant(f)
+                }
+            }
+        )+
+    };
+}
+
+differentiable_enum! {
+    /// Closed set of [Differentiable] functions defined in this module which can be used in a
+    /// homogeneous collection without dynamic dispatch.
+    ///
+    /// This is the so called *enum dispatch* pattern - an alternative to `dyn` trait objects when
+    /// the set of implementations is closed and known upfront. Such an `enum` has a size known at
+    /// compile time, so it can be stored in a homogeneous collection (e.g. [Vec]) directly without
+    /// any heap allocation, and method calls are statically dispatched (and can be inlined)
+    /// instead of going through a *vtable*.
+    ///
+    /// The `enum` and the forwarding implementations are generated by a declarative macro.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::dispatch::*;
+    ///
+    /// let functions: Vec<AnyDifferentiable> = vec![
+    ///     Quadratic::stack_alloc(2., 1., 0.).into(),
+    ///     Trigonometric::Sine.into(),
+    /// ];
+    ///
+    /// for function in functions.iter() {
+    ///     gradient_descent_static(function, 10_000, 0.01);
+    /// }
+    /// ```
+    pub enum AnyDifferentiable {
+        Quadratic(Quadratic),
+        Trigonometric(Trigonometric),
+    }
+}
+
+/// Macro that generates a polynomial `struct` with given coefficients (ordered from the highest
+/// degree down to the absolute term) together with its [Integrable], [Differentiable] and
+/// [TwiceDifferentiable] implementations.
+///
+/// Both the value and the analytic derivatives are computed by the
+/// [Horner's method](https://en.wikipedia.org/wiki/Horner%27s_method) in a single pass: if
+/// `p(x) = p'(x) * x + c` then the derivatives satisfy `dp(x) = dp'(x) * x + p'(x)` and
+/// `d2p(x) = d2p'(x) * x + 2 * dp'(x)`.
+macro_rules! impl_polynomial {
+    ($(#[$meta:meta])* $vis:vis $name:ident { $($coef:ident),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, PartialEq)]
+        $vis struct $name {
+            $(pub $coef: f64),+
+        }
+
+        impl $name {
+            #[inline(always)]
+            pub fn new($($coef: f64),+) -> Self {
+                Self { $($coef),+ }
+            }
+
+            /// Evaluates the value and the first two derivatives at `x` in a single pass
+            #[inline(always)]
+            fn horner(&self, x: f64) -> (f64, f64, f64) {
+                let (mut p, mut dp, mut d2p) = (0., 0., 0.);
+                $(
+                    d2p = d2p * x + 2. * dp;
+                    dp = dp * x + p;
+                    p = p * x + self.$coef;
+                )+
+                (p, dp, d2p)
+            }
+        }
+
+        impl Integrable for $name {
+            #[inline(always)]
+            fn eval(&self, x: f64) -> f64 {
+                self.horner(x).0
+            }
+        }
+
+        impl Differentiable for $name {
+            #[inline(always)]
+            fn grad(&self, x: f64) -> f64 {
+                self.horner(x).1
+            }
+        }
+
+        impl TwiceDifferentiable for $name {
+            #[inline(always)]
+            fn grad2(&self, x: f64) -> f64 {
+                self.horner(x).2
+            }
+        }
+    };
+}
+
+impl_polynomial! {
+    /// Cubic function `f(x) = a*x^3 + b*x^2 + c*x + d` with the derivatives
+    /// `f'(x) = 3a*x^2 + 2b*x + c` and `f''(x) = 6a*x + 2b`.
+    ///
+    /// The definition and trait implementations are generated by a declarative macro.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::dispatch::{Cubic, Differentiable, Integrable, TwiceDifferentiable};
+    ///
+    /// let f = Cubic::new(1., -2., 3., 4.);
+    /// assert_eq!(f.eval(2.), 10.);
+    /// assert_eq!(f.grad(2.), 7.);
+    /// assert_eq!(f.grad2(2.), 8.);
+    /// ```
+    pub Cubic { a, b, c, d }
+}
+
+/// This test shows that if one wants to construct a container ([Vec] in this case) of
+/// [Differentiable] instances, it cannot be done with a *static polymorphic type*.
+///
+/// # Example
+/// ```compile_fail
+/// use rust_examples::dispatch::{Differentiable, Trigonometric};
+///
+/// let _: Vec<Differentiable> = vec![Trigonometric::Sine, Trigonometric::Cosine];
+/// ```
+///
+/// The *size* of this heterogeneous container cannot be known at compile time - this issue is that
+/// the compiler tries to monomorphize [Differentiable] items in the [Vec] but each one might be
+/// different and the collection is dynamic, hence the unknown size.
+///
+/// In consequence, here one **must** use heap-allocated `dyn` instances (i.e. dynamic dispatch)!
+pub struct CannotMonomorphizeDifferentiableInVecTest;
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::f64::consts::FRAC_PI_2;
+    use crate::approx;
+    use rstest::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
 
     const EPS: f64 = 0.00001;
 
@@ -114,6 +663,52 @@ mod tests {
         };
     }
 
+    #[test]
+    fn quadratic_builder() {
+        let built = Quadratic::builder()
+            .with_a(1.)
+            .with_b(2.)
+            .with_c(3.)
+            .build();
+        assert_eq!(built, Quadratic::stack_alloc(1., 2., 3.));
+
+        // The order of the calls does not matter
+        let built = Quadratic::builder()
+            .with_c(3.)
+            .with_a(1.)
+            .with_b(2.)
+            .build();
+        assert_eq!(built, Quadratic::stack_alloc(1., 2., 3.));
+
+        assert_eq!(
+            std::mem::size_of::<QuadraticBuilder<Set, Set, Set>>(),
+            std::mem::size_of::<Quadratic>()
+        );
+    }
+
+    #[rstest]
+    #[case(-2.)]
+    #[case(-0.5)]
+    #[case(0.)]
+    #[case(1.)]
+    #[case(3.5)]
+    fn cubic_gradient(#[case] x: f64) {
+        let f = Cubic::new(2., -3., 0.5, 7.);
+
+        // Analytic derivative `3a*x^2 + 2b*x + c`
+        assert!(approx!(f.grad(x), 6. * x * x - 6. * x + 0.5; 1e-12));
+
+        // Central finite difference approximation
+        let h = 1e-5;
+        let approx_grad = (f.eval(x + h) - f.eval(x - h)) / (2. * h);
+        assert!(
+            approx!(f.grad(x), approx_grad; 1e-6),
+            "{} is not close to {}",
+            f.grad(x),
+            approx_grad
+        );
+    }
+
     #[test]
     fn quadratic() {
         // min { 2*x^2 - x } = -1/8 at x = 1/4
@@ -165,21 +760,96 @@ mod tests {
             gradient_descent_dynamic(function.as_ref(), 10_000, 0.01);
         }
     }
-}
 
-/// This test shows that if one wants to construct a container ([Vec] in this case) of
-/// [Differentiable] instances, it cannot be done with a *static polymorphic type*.
-///
-/// # Example
-/// ```compile_fail
-/// use rust_examples::dispatch::{Differentiable, Trigonometric};
-///
-/// let _: Vec<Differentiable> = vec![Trigonometric::Sine, Trigonometric::Cosine];
-/// ```
-///
-/// The *size* of this heterogeneous container cannot be known at compile time - this issue is that
-/// the compiler tries to monomorphize [Differentiable] items in the [Vec] but each one might be
-/// different and the collection is dynamic, hence the unknown size.
-///
-/// In consequence, here one **must** use heap-allocated `dyn` instances (i.e. dynamic dispatch)!
-pub struct CannotMonomorphizeDifferentiableInVecTest;
+    #[test]
+    fn enum_dispatch() {
+        let functions: Vec<AnyDifferentiable> = vec![
+            Quadratic::stack_alloc(2., 1., 0.).into(),
+            Trigonometric::Sine.into(),
+            Trigonometric::Cosine.into(),
+        ];
+
+        let expected: Vec<Box<dyn TwiceDifferentiable>> = vec![
+            Box::new(Quadratic::stack_alloc(2., 1., 0.)),
+            Box::new(Trigonometric::Sine),
+            Box::new(Trigonometric::Cosine),
+        ];
+
+        // Enum dispatch must behave exactly like the dynamic dispatch
+        for (function, expected) in functions.iter().zip(expected.iter()) {
+            for x in [-1., 0., 0.5, 2.] {
+                assert_eq!(function.grad(x), expected.grad(x));
+                assert_eq!(function.grad2(x), expected.grad2(x));
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::vanilla(Strategy::Vanilla { eta: 0.01 })]
+    #[case::momentum(Strategy::Momentum { eta: 0.01, beta: 0.9 })]
+    #[case::newton(Strategy::Newton)]
+    #[case::adam(Strategy::Adam { eta: 0.01, beta1: 0.9, beta2: 0.999, eps: 1e-8 })]
+    fn minimize_quadratic(#[case] strategy: Strategy) {
+        // min { 2*x^2 - x } = -1/8 at x = 1/4
+        let function = Quadratic::stack_alloc(2., 1., 0.);
+        let x_min = minimize(&function, strategy, 10_000);
+        assert!(
+            approx!(x_min, 0.25; EPS),
+            "{:?} did not converge, got {}",
+            strategy,
+            x_min
+        );
+    }
+
+    #[test]
+    fn newton_converges_faster() {
+        // Local minimum of `x^3 + x^2 - x` is at x = 1/3 (where `3x^2 + 2x - 1 = 0`).
+        //  - Note that Newton's method would reach the minimum of a quadratic function in a single
+        //    step, so a non-quadratic function is necessary to observe the rate of convergence
+        let function = Cubic::new(1., 1., -1., 0.);
+        let optimum = 1. / 3.;
+
+        let gd_rate = convergence_rate(&gradient_descent_trace(&function, 500, 0.01), optimum);
+        assert!(approx!(gd_rate, 1.; 1e-3), "GD is not linear: {}", gd_rate);
+
+        // Few iterations suffice, afterwards the error would drop to the machine precision
+        let trace = newton_trace(&function, 4);
+        assert!(trace.iter().all(|&x| x != optimum));
+
+        let newton_rate = convergence_rate(&trace, optimum);
+        assert!(
+            approx!(newton_rate, 2.; 0.1),
+            "Newton is not quadratic: {}",
+            newton_rate
+        );
+    }
+
+    #[test]
+    fn trace_csv() {
+        let function = Quadratic::stack_alloc(2., 1., 0.);
+        let trace = gradient_descent_trace(&function, 10, 0.01);
+
+        let csv = trace_to_csv(&trace);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("iteration,x"));
+        assert_eq!(lines.next(), Some("0,0"));
+        assert_eq!(lines.count(), trace.len() - 1);
+
+        let csv = trace_with_values_to_csv(&function, &trace);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("iteration,x,value"));
+        assert_eq!(lines.next(), Some("0,0,0"));
+
+        for (line, x) in lines.zip(trace.iter().skip(1)) {
+            let value = line.rsplit(',').next().expect("value column");
+            assert_eq!(value.parse::<f64>(), Ok(function.eval(*x)));
+        }
+        assert_eq!(csv.lines().count(), trace.len() + 1);
+    }
+
+    #[test]
+    fn integrate_sine() {
+        let integral = integrate(&Trigonometric::Sine, 0., PI, 1_000);
+        assert!(approx!(integral, 2.; EPS), "{} is not close to 2", integral);
+    }
+}
@@ -9,6 +9,9 @@
 //!
 //! Naturally, Rust promotes types like [Option] and [Result] to mitigate the amount of possible
 //! non-recoverable situations.
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
 
 /// This function computes `num / d` in a *naive* way that causes the program to *panic* if `d = 0`
 pub fn naive_div(num: i32, d: i32) -> i32 {
@@ -30,6 +33,27 @@ pub fn maybe_div(num: i32, d: i32) -> Option<i32> {
     }
 }
 
+/// Recovers from the division by zero by computing a `fallback` value instead.
+///
+/// The `fallback` is evaluated *lazily*, i.e. only when the error actually occurs. This is the
+/// same as `maybe_div(num, d).unwrap_or_else(fallback)`.
+///
+/// # Example
+/// ```
+/// use rust_examples::errors::div_or;
+///
+/// assert_eq!(div_or(42, 2, || i32::MAX), 21);
+/// assert_eq!(div_or(42, 0, || i32::MAX), i32::MAX);
+/// ```
+pub fn div_or<F: FnOnce() -> i32>(num: i32, d: i32, fallback: F) -> i32 {
+    maybe_div(num, d).unwrap_or_else(fallback)
+}
+
+/// Recovers from the division by zero by substituting the default value (0) for the result.
+pub fn div_or_default(num: i32, d: i32) -> i32 {
+    maybe_div(num, d).unwrap_or_default()
+}
+
 /// This version of division is somewhat artificial but demonstrates another error context, the
 /// [Result].
 ///
@@ -53,6 +77,206 @@ pub fn explained_div(num: i32, d: &str) -> Result<i32, String> {
     }
 }
 
+/// Typed version of the errors produced by [explained_div].
+///
+/// Contrary to a plain [String], an `enum` makes it possible for the caller to `match` on the
+/// error cases and the parse failure keeps the original [ParseIntError].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DivError {
+    /// The divisor was zero
+    Zero,
+    /// The divisor could not be parsed as an integer
+    Parse(ParseIntError),
+}
+
+impl DivError {
+    /// Stable string tag of this error (suitable e.g. for metrics or structured logging).
+    ///
+    /// ```
+    /// use rust_examples::errors::DivError;
+    ///
+    /// assert_eq!(DivError::Zero.kind(), "div_by_zero");
+    /// ```
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Zero => "div_by_zero",
+            Self::Parse(_) => "parse",
+        }
+    }
+
+    /// Whether the caller can sensibly recover from this error.
+    ///
+    /// A division by zero has a well-defined fallback (see [div_or] and [div_or_default]), so it
+    /// is considered recoverable. On the other hand, a malformed divisor means that the input
+    /// itself is invalid and there's no meaningful value to substitute, so such error should be
+    /// reported instead.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Self::Zero => true,
+            Self::Parse(_) => false,
+        }
+    }
+}
+
+/// The messages are the same as the ones produced by [explained_div], so [DivError] is a drop-in
+/// replacement for the [String] errors (e.g. via `to_string()`).
+///
+/// ```
+/// use rust_examples::errors::{explained_div, typed_div};
+///
+/// for d in ["0", "abc"] {
+///     let typed = typed_div(42, d).map_err(|e| e.to_string());
+///     assert_eq!(typed, explained_div(42, d));
+/// }
+/// ```
+impl fmt::Display for DivError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Zero => write!(f, "Division by zero!"),
+            Self::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// The underlying [ParseIntError] is exposed as the [source](Error::source) of the parse error.
+impl Error for DivError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Zero => None,
+            Self::Parse(e) => Some(e),
+        }
+    }
+}
+
+/// Same as [explained_div] but with a typed [DivError].
+pub fn typed_div(num: i32, d: &str) -> Result<i32, DivError> {
+    match d.parse::<i32>() {
+        Ok(div) if div != 0 => Ok(num / div),
+        Ok(_) => Err(DivError::Zero),
+        Err(e) => Err(DivError::Parse(e)),
+    }
+}
+
+/// Errors of [compute] which compose [DivError] with other arithmetic failures.
+///
+/// Contrary to the hand-rolled [Display](fmt::Display) and [Error] impls of [DivError], these are
+/// derived by [`thiserror`](https://crates.io/crates/thiserror) which is the ecosystem-standard
+/// approach for library errors. The `#[from]` attributes generate the [From] conversions, so the
+/// `?` operator lifts the underlying errors automatically.
+#[cfg(feature = "thiserror")]
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum CalcError {
+    /// The expression is not of the form `a / b`
+    #[error("Malformed expression: {0:?}")]
+    Malformed(String),
+    /// An operand could not be parsed as an integer
+    #[error("Invalid operand: {0}")]
+    Parse(#[from] ParseIntError),
+    /// The division itself failed
+    ///
+    /// Note that [compute] only ever produces [`DivError::Zero`] here, a malformed operand
+    /// (including the divisor) is always reported as [`CalcError::Parse`].
+    #[error(transparent)]
+    Div(#[from] DivError),
+    /// The result does not fit into an [i32] (i.e. `i32::MIN / -1`)
+    #[error("Arithmetic overflow!")]
+    Overflow,
+}
+
+/// Parses and evaluates a trivial expression of the form `a / b` where both operands are [i32]s.
+///
+/// # Example
+/// ```
+/// use rust_examples::errors::{compute, CalcError, DivError};
+///
+/// assert_eq!(compute("42 / 2"), Ok(21));
+/// assert_eq!(compute("42 / 0"), Err(CalcError::Div(DivError::Zero)));
+/// assert_eq!(compute("42 / 0").unwrap_err().to_string(), "Division by zero!");
+/// ```
+#[cfg(feature = "thiserror")]
+pub fn compute(expr: &str) -> Result<i32, CalcError> {
+    let (a, b) = expr
+        .split_once('/')
+        .ok_or_else(|| CalcError::Malformed(expr.to_string()))?;
+
+    let a = a.trim().parse::<i32>()?;
+    let b = b.trim().parse::<i32>()?;
+
+    if b == 0 {
+        return Err(DivError::Zero.into());
+    }
+
+    a.checked_div(b).ok_or(CalcError::Overflow)
+}
+
+/// Divides `num` by each of the `divisors` and returns either all the results or *all* the
+/// errors paired with the positions of divisors that caused them.
+///
+/// This is a *validation-style* error handling which *accumulates* errors. Contrary to the
+/// *monadic* short-circuit of collecting into a [Result] (see
+/// [ResultIterExt::collect_or_first_err]), every division is attempted so that the caller gets
+/// a complete report of what went wrong.
+///
+/// # Example
+/// ```
+/// use rust_examples::errors::{div_all, DivError};
+///
+/// assert_eq!(div_all(42, &["2", "7"]), Ok(vec![21, 6]));
+///
+/// let errors = div_all(42, &["0", "2", "0"]).unwrap_err();
+/// assert_eq!(errors, vec![(0, DivError::Zero), (2, DivError::Zero)]);
+/// ```
+pub fn div_all(num: i32, divisors: &[&str]) -> Result<Vec<i32>, Vec<(usize, DivError)>> {
+    let mut results = Vec::with_capacity(divisors.len());
+    let mut errors = Vec::new();
+
+    for (i, d) in divisors.iter().enumerate() {
+        match typed_div(num, d) {
+            Ok(result) => results.push(result),
+            Err(e) => errors.push((i, e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(results)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Extension trait which gives explicit names to common *non-accumulating* policies of collecting
+/// an iterator of [Result]s.
+///
+/// Being an *extension trait*, it is implemented for all iterators over results and its methods
+/// can be called as if they were defined on [Iterator] itself.
+///
+/// # Example
+/// ```
+/// use rust_examples::errors::{explained_div, ResultIterExt};
+///
+/// let divs = || ["2", "0", "7"].iter().map(|d| explained_div(42, d));
+///
+/// assert_eq!(divs().collect_or_first_err(), Err("Division by zero!".to_string()));
+/// assert_eq!(divs().collect_oks(), vec![21, 6]);
+/// ```
+pub trait ResultIterExt<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    /// Collects all [`Ok`] values or returns the first [`Err`] encountered, in which case the rest
+    /// of the iterator is not consumed.
+    ///
+    /// This is just a named and discoverable version of what `collect::<Result<Vec<_>, _>>()`
+    /// does in `std`.
+    fn collect_or_first_err(self) -> Result<Vec<T>, E> {
+        self.collect()
+    }
+
+    /// Collects all [`Ok`] values and silently drops all errors.
+    fn collect_oks(self) -> Vec<T> {
+        self.filter_map(Result::ok).collect()
+    }
+}
+
+impl<I, T, E> ResultIterExt<T, E> for I where I: Iterator<Item = Result<T, E>> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,6 +302,32 @@ mod tests {
         assert_eq!(maybe_div(num, d), expected);
     }
 
+    #[rstest]
+    #[case::ok(42, 2, 21, false)]
+    #[case::zero_div(42, 0, -1, true)]
+    fn div_or_recovers(
+        #[case] num: i32,
+        #[case] d: i32,
+        #[case] expected: i32,
+        #[case] fallback_called: bool,
+    ) {
+        let mut called = false;
+        let result = div_or(num, d, || {
+            called = true;
+            -1
+        });
+
+        assert_eq!(result, expected);
+        assert_eq!(called, fallback_called);
+    }
+
+    #[rstest]
+    #[case::ok(42, 2, 21)]
+    #[case::zero_div(42, 0, 0)]
+    fn div_or_default_recovers(#[case] num: i32, #[case] d: i32, #[case] expected: i32) {
+        assert_eq!(div_or_default(num, d), expected);
+    }
+
     #[rstest]
     // In standard case we get an Ok result
     #[case::ok(42, "2", Ok(21))]
@@ -89,4 +339,87 @@ mod tests {
     fn explained_div_works(#[case] num: i32, #[case] d: &str, #[case] expected: Result<i32, &str>) {
         assert_eq!(explained_div(num, d), expected.map_err(String::from));
     }
+
+    #[rstest]
+    fn collect_short_circuits() {
+        let mut evaluated = 0;
+
+        let result = ["2", "abc", "0", "7"]
+            .iter()
+            .map(|d| {
+                evaluated += 1;
+                explained_div(42, d)
+            })
+            .collect_or_first_err();
+
+        assert_eq!(result, Err("invalid digit found in string".to_string()));
+        assert_eq!(evaluated, 2);
+
+        let result = ["2", "7"].iter().map(|d| explained_div(42, d));
+        assert_eq!(result.collect_or_first_err(), Ok(vec![21, 6]));
+    }
+
+    #[rstest]
+    fn collect_drops_errors() {
+        let result = ["2", "abc", "0", "7"]
+            .iter()
+            .map(|d| explained_div(42, d))
+            .collect_oks();
+
+        assert_eq!(result, vec![21, 6]);
+    }
+
+    #[rstest]
+    #[case::zero("0", "div_by_zero", true)]
+    #[case::parse("abc", "parse", false)]
+    fn div_error_categories(#[case] d: &str, #[case] kind: &str, #[case] recoverable: bool) {
+        let error = typed_div(42, d).expect_err("This case should fail");
+        assert_eq!(error.kind(), kind);
+        assert_eq!(error.is_recoverable(), recoverable);
+    }
+
+    #[rstest]
+    fn div_error_display_and_source() {
+        assert_eq!(format!("{}", DivError::Zero), "Division by zero!");
+        assert!(DivError::Zero.source().is_none());
+
+        let error = typed_div(42, "abc").expect_err("This case should fail");
+        let parse_error = "abc".parse::<i32>().expect_err("This case should fail");
+        assert_eq!(error.to_string(), parse_error.to_string());
+
+        let source = error.source().expect("parse error has a source");
+        assert_eq!(source.to_string(), parse_error.to_string());
+        assert!(source.downcast_ref::<ParseIntError>().is_some());
+    }
+
+    #[rstest]
+    fn div_all_accumulates_errors() {
+        let errors = div_all(42, &["2", "0", "abc", "7"]).expect_err("This case should fail");
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0], (1, DivError::Zero));
+        assert!(matches!(errors[1], (2, DivError::Parse(_))));
+
+        assert_eq!(div_all(42, &["2", "7"]), Ok(vec![21, 6]));
+    }
+
+    #[cfg(feature = "thiserror")]
+    #[rstest]
+    #[case::ok("42 / 2", Ok(21))]
+    #[case::overflow("-2147483648 / -1", Err(CalcError::Overflow))]
+    #[case::zero_div("42 / 0", Err(CalcError::Div(DivError::Zero)))]
+    #[case::malformed("42", Err(CalcError::Malformed("42".to_string())))]
+    fn compute_expression(#[case] expr: &str, #[case] expected: Result<i32, CalcError>) {
+        assert_eq!(compute(expr), expected);
+    }
+
+    #[cfg(feature = "thiserror")]
+    #[rstest]
+    #[case::numerator("abc / 2")]
+    #[case::denominator("42 / abc")]
+    fn compute_parse_failure(#[case] expr: &str) {
+        let err = compute(expr).unwrap_err();
+        assert!(matches!(err, CalcError::Parse(_)), "unexpected {:?}", err);
+        assert!(err.source().is_some());
+    }
 }
@@ -1,3 +1,34 @@
+//! Support materials for Rust tech-talks.
+//!
+//! # Features
+//!  - `std` (default): enables the modules which depend on the standard library (e.g. for I/O,
+//!    printing or reference counting)
+//!  - `no_std`: builds the library with `#![no_std]` (when `std` is disabled) so that the
+//!    [rsqrt], [typing], [dependent], [brands] and [macros] modules can be used in embedded
+//!    contexts with just `core` and `alloc`. Square roots are then computed by `libm`.
+//!
+//! For instance, the following runs with only the `core` prelude:
+#![cfg_attr(not(feature = "std"), doc = "```")]
+#![cfg_attr(feature = "std", doc = "```ignore")]
+//! #![no_std]
+//! // Note: `std` is linked only to run this example, the library itself does not use it
+//! extern crate std;
+//!
+//! use rust_examples::rsqrt::PositiveFloat;
+//!
+//! fn main() {
+//!     let x = PositiveFloat::new(4.0).expect("positive normal float");
+//!     let estimate = x.fast_rsqrt::<2>().inner();
+//!     assert!((estimate - 0.5).abs() < 1e-3);
+//! }
+//! ```
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("Either the `std` or the `no_std` (resp. `libm`) feature must be enabled");
+
+extern crate alloc;
+
 #[cfg(test)]
 extern crate quickcheck;
 
@@ -5,15 +36,22 @@ extern crate quickcheck;
 #[macro_use(quickcheck)]
 extern crate quickcheck_macros;
 
+#[cfg(feature = "std")]
 pub mod adts;
 pub mod brands;
+#[cfg(feature = "std")]
 pub mod collect;
 pub mod dependent;
+#[cfg(feature = "std")]
 pub mod dispatch;
+#[cfg(feature = "std")]
 pub mod errors;
 pub mod macros;
+#[cfg(feature = "std")]
 pub mod memory;
+#[cfg(feature = "std")]
 pub mod orphan;
+#[cfg(feature = "std")]
 pub mod rc;
 pub mod rsqrt;
 pub mod typing;
@@ -23,6 +23,37 @@ macro_rules! approx {
     };
 }
 
+/// Macro that asserts that two slices of [f64] have the same length and are elementwise
+/// [approx!] equal within given `$eps`.
+///
+/// Contrary to [approx!], this is a statement which panics with the first index at which the
+/// slices differ. Any expression which can be borrowed as `&[f64]` (e.g. an array or a [Vec]) can
+/// be passed as an argument.
+/// ```
+/// use rust_examples::assert_approx_slice;
+///
+/// assert_approx_slice!([1.0, 2.0], vec![1.0, 2.0 + 1e-9]; 1e-6);
+/// ```
+/// ```should_panic
+/// use rust_examples::assert_approx_slice;
+///
+/// assert_approx_slice!([1.0, 2.0], [1.0, 2.1]; 1e-6);
+/// ```
+#[macro_export]
+macro_rules! assert_approx_slice {
+    ($a:expr, $b:expr; $eps:expr) => {{
+        let (a, b): (&[f64], &[f64]) = (&$a, &$b);
+        let eps = $eps;
+        assert_eq!(a.len(), b.len(), "slices differ in length");
+        for (i, (x, y)) in a.iter().zip(b.iter()).enumerate() {
+            let close = $crate::approx!(*x, *y; eps);
+            if !close {
+                panic!("slices differ at index {}: {} is not within {} of {}", i, x, eps, y);
+            }
+        }
+    }};
+}
+
 /// Type class that defines instances having a known maximum value
 pub trait MaxValue {
     /// Accessor to the maximum value
@@ -57,10 +88,69 @@ macro_rules! impl_max_value {
 // This is the where we actually create all the `impl`s
 impl_max_value!(u32, i32, u64, i64);
 
+/// Type class that defines instances having a known minimum value
+pub trait MinValue {
+    /// Accessor to the minimum value
+    fn min_value() -> Self;
+}
+
+/// Macro that automates implementation of [MinValue] for arbitrary types having `MIN` member (see
+/// [impl_max_value]).
+#[macro_export]
+macro_rules! impl_min_value {
+    ($($t:ty),+) => {
+        $(
+            impl $crate::macros::MinValue for $t {
+                fn min_value() -> Self {
+                    <$t>::MIN
+                }
+            }
+        )+
+    };
+}
+
+impl_min_value!(u32, i32, u64, i64);
+
+/// Clamps `v` into the closed range `[T::min_value(), T::max_value()]`.
+///
+/// Contrary to the macros above which *implement* [MinValue] and [MaxValue], this function
+/// *consumes* these type classes generically - it works for any type with both instances, no
+/// matter whether these were generated by [impl_min_value] and [impl_max_value] or written by
+/// hand. For primitive integers the range covers all the values, so these pass through unchanged.
+///
+/// ```
+/// use rust_examples::macros::clamp_to_bounds;
+/// use rust_examples::{impl_max_value, impl_min_value};
+///
+/// #[derive(Debug, PartialEq, PartialOrd)]
+/// struct Percent(u8);
+///
+/// impl Percent {
+///     const MIN: Self = Percent(0);
+///     const MAX: Self = Percent(100);
+/// }
+///
+/// impl_min_value!(Percent);
+/// impl_max_value!(Percent);
+///
+/// assert_eq!(clamp_to_bounds(Percent(42)), Percent(42));
+/// assert_eq!(clamp_to_bounds(Percent(150)), Percent(100));
+/// ```
+pub fn clamp_to_bounds<T: MinValue + MaxValue + PartialOrd>(v: T) -> T {
+    let (min, max) = (T::min_value(), T::max_value());
+    if v < min {
+        min
+    } else if v > max {
+        max
+    } else {
+        v
+    }
+}
+
 /// Macro that counts any input tokens at compilation time (i.e. resulting in a `const` value) with
 /// no residual memory footprint.
 /// ```
-/// use rust_examples::{count, substitute};
+/// use rust_examples::count;
 ///
 /// const COUNT: usize = count!(1, 2, 3);
 /// ```
@@ -71,7 +161,7 @@ impl_max_value!(u32, i32, u64, i64);
 #[macro_export]
 macro_rules! count {
     ($($item:tt),*) => {
-        <[()]>::len(&[$(substitute!($item ())),*])
+        <[()]>::len(&[$($crate::substitute!($item ())),*])
     };
 }
 
@@ -83,9 +173,71 @@ macro_rules! substitute {
     };
 }
 
+/// Macro that builds a [HashMap](std::collections::HashMap) from `key => value` pairs.
+///
+/// The type of the map is inferred from the entries and the map is pre-allocated with the exact
+/// capacity which is computed by [count!](crate::count) at compilation time.
+/// ```
+/// use rust_examples::hashmap;
+///
+/// let map = hashmap! {
+///     "a" => 1,
+///     "b" => 2,
+/// };
+///
+/// assert_eq!(map.len(), 2);
+/// assert_eq!(map["b"], 2);
+/// ```
+#[macro_export]
+macro_rules! hashmap {
+    ($($key:expr => $value:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = ::std::collections::HashMap::with_capacity($crate::count!($($key),*));
+        $(map.insert($key, $value);)*
+        map
+    }};
+}
+
+/// Computes the `n`th Fibonacci number.
+///
+/// This is the plain (exponential) recursion without any memoization. Because it's a `const fn`,
+/// the recursion is evaluated by the *const evaluator* when called in a `const` context (see
+/// [const_fib!](crate::const_fib)) and the result is then just a constant in the binary. The same
+/// function can still be called at runtime as any ordinary function.
+pub const fn fib(n: u64) -> u64 {
+    match n {
+        0 => 0,
+        1 => 1,
+        n => fib(n - 1) + fib(n - 2),
+    }
+}
+
+/// Macro that computes the `$n`th Fibonacci number at compilation time.
+///
+/// The expansion binds the result of [fib](crate::macros::fib) to an inner `const` item which
+/// forces the evaluation to happen at compile time, no matter the context in which the macro is
+/// used. So the result is a plain constant without any `lazy_static` or runtime initialization.
+/// ```
+/// use rust_examples::const_fib;
+///
+/// const FIB: u64 = const_fib!(10);
+/// assert_eq!(FIB, 55);
+/// ```
+///
+/// Note that since the computation happens at compile time, an overflow would be reported as a
+/// compilation error rather than a runtime panic.
+#[macro_export]
+macro_rules! const_fib {
+    ($n:expr) => {{
+        const FIB: u64 = $crate::macros::fib($n);
+        FIB
+    }};
+}
+
 #[macro_use]
 #[cfg(test)]
 mod tests {
+    use crate::macros::{clamp_to_bounds, MaxValue, MinValue};
     use rstest::*;
 
     #[rstest]
@@ -95,12 +247,70 @@ mod tests {
         assert!(approx!(x, y))
     }
 
+    #[rstest]
+    fn approx_slices() {
+        assert_approx_slice!([], []; 1e-6);
+        assert_approx_slice!([1.0, -2.5, 3.0], [1.0 + 1e-9, -2.5, 3.0 - 1e-9]; 1e-6);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "slices differ at index 1: 2 is not within 0.001 of 2.1")]
+    fn approx_slices_differ() {
+        assert_approx_slice!([1.0, 2.0, 3.0], [1.0, 2.1, 3.5]; 0.001);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "slices differ in length")]
+    fn approx_slices_differ_in_length() {
+        assert_approx_slice!([1.0, 2.0], [1.0]; 0.001);
+    }
+
     #[rstest]
     fn max_values() {
-        assert_eq!(u32::max_value(), u32::MAX);
-        assert_eq!(i32::max_value(), i32::MAX);
-        assert_eq!(u64::max_value(), u64::MAX);
-        assert_eq!(i64::max_value(), i64::MAX);
+        assert_eq!(<u32 as MaxValue>::max_value(), u32::MAX);
+        assert_eq!(<i32 as MaxValue>::max_value(), i32::MAX);
+        assert_eq!(<u64 as MaxValue>::max_value(), u64::MAX);
+        assert_eq!(<i64 as MaxValue>::max_value(), i64::MAX);
+    }
+
+    #[rstest]
+    fn min_values() {
+        assert_eq!(<u32 as MinValue>::min_value(), u32::MIN);
+        assert_eq!(<i32 as MinValue>::min_value(), i32::MIN);
+        assert_eq!(<u64 as MinValue>::min_value(), u64::MIN);
+        assert_eq!(<i64 as MinValue>::min_value(), i64::MIN);
+    }
+
+    #[rstest]
+    #[case::zero(0)]
+    #[case::positive(42)]
+    #[case::negative(-42)]
+    #[case::min(i32::MIN)]
+    #[case::max(i32::MAX)]
+    fn clamp_integers(#[case] v: i32) {
+        assert_eq!(clamp_to_bounds(v), v);
+        assert_eq!(clamp_to_bounds(v as i64), v as i64);
+        assert_eq!(clamp_to_bounds(v.unsigned_abs()), v.unsigned_abs());
+    }
+
+    #[rstest]
+    fn clamp_derived_bounds() {
+        /// Integer restricted to a custom range with instances generated by the macros
+        #[derive(Debug, PartialEq, PartialOrd)]
+        struct Level(i32);
+
+        impl Level {
+            const MIN: Self = Level(-10);
+            const MAX: Self = Level(10);
+        }
+
+        impl_min_value!(Level);
+        impl_max_value!(Level);
+
+        assert_eq!(clamp_to_bounds(Level(3)), Level(3));
+        assert_eq!(clamp_to_bounds(Level(-10)), Level(-10));
+        assert_eq!(clamp_to_bounds(Level(42)), Level(10));
+        assert_eq!(clamp_to_bounds(Level(-42)), Level(-10));
     }
 
     #[rstest]
@@ -109,4 +319,32 @@ mod tests {
         assert_eq!(count!(1), 1);
         assert_eq!(count!([1, 2], [], [0, 1, 3]), 3);
     }
+
+    #[rstest]
+    fn build_hashmap() {
+        let map = hashmap! {"a" => 1, "b" => 2, "c" => 1 + 2};
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(map.get("b"), Some(&2));
+        assert_eq!(map.get("c"), Some(&3));
+        assert_eq!(map.get("d"), None);
+
+        let map = hashmap! {
+            (1, 2) => "x",
+            (3, 4) => "y",
+        };
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&(3, 4)], "y");
+
+        let map: std::collections::HashMap<u8, u8> = hashmap! {};
+        assert!(map.is_empty());
+    }
+
+    #[rstest]
+    fn const_fibonacci() {
+        const FIB_10: u64 = const_fib!(10);
+        assert_eq!(FIB_10, 55);
+        assert_eq!(const_fib!(0), 0);
+        assert_eq!(const_fib!(1), 1);
+    }
 }
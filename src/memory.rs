@@ -7,23 +7,52 @@
 //!  * [double free](https://owasp.org/www-community/vulnerabilities/Doubly_freeing_memory)
 //!
 //! and other typical [memory errors](https://en.wikipedia.org/wiki/Memory_safety).
+use std::fmt::Debug;
+use std::ops::{Add, Sub};
 
-#[derive(Debug)]
-pub struct Point2D {
-    pub x: f64,
-    pub y: f64,
+/// Point in a plane with coordinates of a generic type `T`.
+///
+/// Note that [Point] intentionally does not implement [Copy] (even if `T` does) so that passing it
+/// by value *moves* the ownership.
+#[derive(Debug, PartialEq)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
 }
 
-pub fn take_ownership(p: Point2D) {
+/// Point with [f64] coordinates
+pub type Point2D = Point<f64>;
+
+/// Points are added coordinate-wise. Since [Add::add] takes both operands by value, the addition
+/// consumes (moves) both points.
+///
+/// ```
+/// use rust_examples::memory::Point;
+///
+/// let p = Point { x: 1, y: 2 } + Point { x: 3, y: 4 };
+/// assert_eq!(p, Point { x: 4, y: 6 });
+/// ```
+impl<T: Add> Add for Point<T> {
+    type Output = Point<T::Output>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+pub fn take_ownership<T: Debug>(p: Point<T>) {
     println!("The point is {:?}", p)
     // Rust's compiler implicitly adds `drop(p)` which physically frees the memory held by `p`
 }
 
-pub fn borrow_point_immutably(p: &Point2D) {
+pub fn borrow_point_immutably<T: Debug>(p: &Point<T>) {
     println!("The point is {:?}", p)
 }
 
-pub fn borrow_twice(x: &Point2D, y: &Point2D) {
+pub fn borrow_twice<T: Debug>(x: &Point<T>, y: &Point<T>) {
     println!("x = {:?} and y = {:?}", x, y);
 }
 
@@ -33,13 +62,42 @@ pub fn borrow_point_mutably(p: &mut Point2D) {
     println!("New point is {:?}", p);
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct RGBColor(u8, u8, u8);
 
 pub fn show_color(color: RGBColor) {
     println!("The color is {:?}", color)
 }
 
+/// Colors are added channel by channel with *saturation*, so e.g. `200 + 100 = 255`.
+///
+/// Note that [RGBColor] is [Copy], so the operator takes its operands by value without moving
+/// them - these are just copied (which is cheap for three bytes).
+impl Add for RGBColor {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(
+            self.0.saturating_add(rhs.0),
+            self.1.saturating_add(rhs.1),
+            self.2.saturating_add(rhs.2),
+        )
+    }
+}
+
+/// Colors are subtracted channel by channel with *saturation*, so e.g. `100 - 200 = 0`.
+impl Sub for RGBColor {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(
+            self.0.saturating_sub(rhs.0),
+            self.1.saturating_sub(rhs.1),
+            self.2.saturating_sub(rhs.2),
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct Palette<'a> {
     pub colors: &'a [RGBColor],
@@ -49,6 +107,102 @@ pub fn show_palette(palette: &Palette) {
     println!("Color palette: {:?}", palette);
 }
 
+/// Palette which borrows its colors *exclusively*, so that they can be modified in place.
+///
+/// While a [MutPalette] is alive, there can be no other reference to the colors, not even a shared
+/// one (see [AliasingXorMutabilityTest]). Once it's dropped, the borrow ends and the owner of the
+/// colors can use them again.
+#[derive(Debug)]
+pub struct MutPalette<'a> {
+    pub colors: &'a mut [RGBColor],
+}
+
+/// Brightens each color in the `palette` by adding `amount` to all its channels (saturating at
+/// [u8::MAX]).
+pub fn brighten(palette: &mut MutPalette, amount: u8) {
+    for RGBColor(r, g, b) in palette.colors.iter_mut() {
+        *r = r.saturating_add(amount);
+        *g = g.saturating_add(amount);
+        *b = b.saturating_add(amount);
+    }
+}
+
+/// This test shows that in *safe* Rust one cannot compile a code that would result in
+/// [double free](https://owasp.org/www-community/vulnerabilities/Doubly_freeing_memory).
+///
+/// # Example
+/// ```compile_fail
+/// struct Data;
+///
+/// let data = Data;
+///
+/// drop(data); // Free the memory once
+/// drop(data); // Free the memory again
+/// ```
+pub struct DoubleFeeTest;
+
+/// This test shows that in *safe* Rust one cannot compile a code that would result in
+/// [use after free](https://owasp.org/www-community/vulnerabilities/Using_freed_memory).
+///
+/// # Example
+/// ```compile_fail
+/// struct Data;
+///
+/// fn use_data(data: Data) {
+///     // <arbitry code using the data>
+///     drop(data) // Exaplicit call to `drop` the data
+/// }
+///
+/// let data = Data;
+/// // Use data once and free them afterwards
+/// use_data(data);
+/// // Use data again after free
+/// use_data(data);
+/// ```
+pub struct UseAfterFreeTest;
+
+/// This test demonstrates the **Aliasing XOR Mutability** principle enforced by Rust. This concept
+/// states that at any time there can be either
+///  * multiple shared references
+///  * or single exclusive reference
+///
+/// # Example
+/// ```compile_fail
+/// #[derive(Debug)]
+/// struct Data;
+///
+/// fn use_data(_data: &Data) {}
+///
+/// let mut data = Data;
+///
+/// let shared = &data;
+/// let exclusive = &mut data;
+///
+/// // Using read-only reference while there exists a reference with exclusive access
+/// use_data(shared);
+/// ```
+pub struct AliasingXorMutabilityTest;
+
+/// This test demonstrates that in Rust each allocated memory has **exactly one owner**. Conversely,
+/// there cannot be two owners of the same value and ownership can only be moved around.
+///
+/// # Example
+/// ```compile_fail
+/// struct Data;
+///
+/// fn use_data(_data: &Data) {}
+///
+/// let old_owner = Data;
+/// use_data(&old_owner);
+///
+/// let new_owner = old_owner; // Value has been moved at this point
+/// use_data(&new_owner);
+///
+/// // This is no longer allowed!
+/// use_data(&old_owner);
+/// ```
+pub struct ExclusiveOwnershipTest;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,6 +246,25 @@ mod tests {
         assert_eq!(3., p2.x);
     }
 
+    #[test]
+    fn generic_points() {
+        let p = Point { x: 1, y: 2 };
+        let q = Point { x: 3, y: -4 };
+        borrow_twice(&p, &q);
+
+        // Both `p` and `q` are moved into the addition, so they can't be used afterwards
+        let r = p + q;
+        assert_eq!(r, Point { x: 4, y: -2 });
+
+        // The same ownership rules apply to any choice of `T`
+        take_ownership(r);
+
+        let p: Point2D = Point { x: 0.5, y: 1. } + Point { x: 0.25, y: 2. };
+        borrow_point_immutably(&p);
+        assert_eq!(p, Point { x: 0.75, y: 3. });
+        take_ownership(p);
+    }
+
     #[test]
     fn call_by_value() {
         let color = RGBColor(128, 0, 128);
@@ -106,6 +279,29 @@ mod tests {
         show_color(color);
     }
 
+    #[test]
+    fn color_arithmetic() {
+        let purple = RGBColor(128, 0, 128);
+        let gray = RGBColor(100, 100, 100);
+
+        assert_eq!(purple + gray, RGBColor(228, 100, 228));
+        assert_eq!(purple - gray, RGBColor(28, 0, 28));
+
+        // Channels saturate at both ends
+        assert_eq!(
+            RGBColor(200, 255, 0) + RGBColor(100, 1, 0),
+            RGBColor(255, 255, 0)
+        );
+        assert_eq!(
+            RGBColor(100, 0, 255) - RGBColor(200, 1, 0),
+            RGBColor(0, 0, 255)
+        );
+
+        // Both operands are copied, so they can still be used
+        show_color(purple);
+        show_color(gray);
+    }
+
     #[test]
     fn lifetimes() {
         let colors = vec![RGBColor(1, 1, 1), RGBColor(2, 2, 2)];
@@ -129,80 +325,26 @@ mod tests {
             show_color(color);
         }
     }
-}
 
-/// This test shows that in *safe* Rust one cannot compile a code that would result in
-/// [double free](https://owasp.org/www-community/vulnerabilities/Doubly_freeing_memory).
-///
-/// # Example
-/// ```compile_fail
-/// struct Data;
-///
-/// let data = Data;
-///
-/// drop(data); // Free the memory once
-/// drop(data); // Free the memory again
-/// ```
-pub struct DoubleFeeTest;
+    #[test]
+    fn mutable_lifetimes() {
+        let mut colors = vec![RGBColor(1, 2, 3), RGBColor(100, 200, 250)];
 
-/// This test shows that in *safe* Rust one cannot compile a code that would result in
-/// [use after free](https://owasp.org/www-community/vulnerabilities/Using_freed_memory).
-///
-/// # Example
-/// ```compile_fail
-/// struct Data;
-///
-/// fn use_data(data: Data) {
-///     // <arbitry code using the data>
-///     drop(data) // Exaplicit call to `drop` the data
-/// }
-///
-/// let data = Data;
-/// // Use data once and free them afterwards
-/// use_data(data);
-/// // Use data again after free
-/// use_data(data);
-/// ```
-pub struct UseAfterFreeTest;
+        {
+            // The palette holds an exclusive reference to the colors, so while it's alive, reading
+            // `colors` directly (e.g. `show_color(colors[0])`) would not compile.
+            let mut palette = MutPalette {
+                colors: &mut colors,
+            };
+            brighten(&mut palette, 10);
+            brighten(&mut palette, 0);
 
-/// This test demonstrates the **Aliasing XOR Mutability** principle enforced by Rust. This concept
-/// states that at any time there can be either
-///  * multiple shared references
-///  * or single exclusive reference
-///
-/// # Example
-/// ```compile_fail
-/// #[derive(Debug)]
-/// struct Data;
-///
-/// fn use_data(_data: &Data) {}
-///
-/// let mut data = Data;
-///
-/// let shared = &data;
-/// let exclusive = &mut data;
-///
-/// // Using read-only reference while there exists a reference with exclusive access
-/// use_data(shared);
-/// ```
-pub struct AliasingXorMutabilityTest;
+            // At this point palette is dropped and the exclusive borrow ends.
+        }
 
-/// This test demonstrates that in Rust each allocated memory has **exactly one owner**. Conversely,
-/// there cannot be two owners of the same value and ownership can only be moved around.
-///
-/// # Example
-/// ```compile_fail
-/// struct Data;
-///
-/// fn use_data(_data: &Data) {}
-///
-/// let old_owner = Data;
-/// use_data(&old_owner);
-///
-/// let new_owner = old_owner; // Value has been moved at this point
-/// use_data(&new_owner);
-///
-/// // This is no longer allowed!
-/// use_data(&old_owner);
-/// ```
-pub struct ExclusiveOwnershipTest;
+        // The modifications were made to the underlying vector which can now be used again
+        assert_eq!(colors, vec![RGBColor(11, 12, 13), RGBColor(110, 210, 255)]);
+        colors.push(RGBColor(0, 0, 0));
+        show_palette(&Palette { colors: &colors });
+    }
+}
@@ -115,23 +115,6 @@ pub mod module_a {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::orphan::model::Entity;
-    use crate::orphan::module_a::prioritize;
-
-    #[test]
-    fn idempotent_prioritize() {
-        let mut entities = vec![Entity::X, Entity::Y];
-
-        prioritize(&mut entities);
-        assert_eq!(entities, vec![Entity::Y, Entity::X]);
-
-        prioritize(&mut entities);
-        assert_eq!(entities, vec![Entity::Y, Entity::X]);
-    }
-}
-
 /// This test demonstrates that Rust disallows *Orphan Instances*.
 ///
 /// # Variation of the *Hash table Problem*
@@ -182,3 +165,20 @@ mod tests {
 /// }
 /// ```
 pub struct OrphanInstanceTest;
+
+#[cfg(test)]
+mod tests {
+    use crate::orphan::model::Entity;
+    use crate::orphan::module_a::prioritize;
+
+    #[test]
+    fn idempotent_prioritize() {
+        let mut entities = vec![Entity::X, Entity::Y];
+
+        prioritize(&mut entities);
+        assert_eq!(entities, vec![Entity::Y, Entity::X]);
+
+        prioritize(&mut entities);
+        assert_eq!(entities, vec![Entity::Y, Entity::X]);
+    }
+}
@@ -7,6 +7,12 @@
 //!  1. [Rc] smart pointer which implements [Clone] by imcrementing reference counter and returning
 //!     a cheap copy of itself with the same data reference (i.e. *shallow copy* at the cost of an
 //!     additional counter)
+//!  1. [Cow] (*clone-on-write*) smart pointer which can either borrow or own the data and is only
+//!     *deep copied* when it's owned or when mutable access is requested via [Cow::to_mut]
+//!
+//! Finally, [Shared] demonstrates *interior mutability* of data shared via an [Rc].
+use std::borrow::Cow;
+use std::cell::{Ref, RefCell, RefMut};
 use std::fmt::Debug;
 use std::rc::Rc;
 
@@ -23,8 +29,8 @@ impl Clone for Data {
 
 /// Container for [Data] allocated and owned in various ways.
 ///
-/// This class can derive [Clone] because [Data] are [Clone] and so are [Box], [Rc] and shared
-/// references `&'a`.
+/// This class can derive [Clone] because [Data] are [Clone] and so are [Box], [Rc], [Cow] and
+/// shared references `&'a`.
 #[derive(Clone, Debug)]
 pub struct Container<'a> {
     /// Owned data located on the *stack*
@@ -35,6 +41,62 @@ pub struct Container<'a> {
     pub heap_owned: Box<Data>,
     /// Reference counting pointer to shared data located on the *heap*
     pub heap_shared: Rc<Data>,
+    /// Clone-on-write pointer which either borrows data that must outlive `'a` or owns them
+    pub cow: Cow<'a, Data>,
+}
+
+/// Returns the number of *strong* and *weak* references ([Rc::strong_count] and
+/// [Rc::weak_count]) to the [Data] shared by the [`heap_shared`](Container::heap_shared) pointer
+/// of given container.
+///
+/// Cloning a [Container] shallow copies its [Rc], so the sharing can be observed directly on the
+/// reference counts instead of the counter inside [Data] (which stays unchanged).
+pub fn strong_weak_counts(c: &Container) -> (usize, usize) {
+    (
+        Rc::strong_count(&c.heap_shared),
+        Rc::weak_count(&c.heap_shared),
+    )
+}
+
+/// Shared and mutable [Data] - a combination of a reference counting pointer [Rc] with a
+/// [RefCell] providing *interior mutability*.
+///
+/// Cloning [Shared] is shallow (just like cloning an [Rc]), so all the clones point to the same
+/// cell and a mutation through one of them is visible through all the others.
+///
+/// Note that an [Rc] alone only provides shared (immutable) access. [RefCell] moves the borrow
+/// checking from compile time to *runtime* - it keeps track of active borrows (at the cost of
+/// an additional counter and a check on each borrow) and *panics* if the rules (single mutable
+/// or many immutable borrows) are violated.
+#[derive(Clone, Debug, Default)]
+pub struct Shared(Rc<RefCell<Data>>);
+
+impl Shared {
+    /// Immutably borrows the shared [Data].
+    ///
+    /// # Panics
+    /// Panics if the data are currently mutably borrowed.
+    pub fn borrow(&self) -> Ref<'_, Data> {
+        self.0.borrow()
+    }
+
+    /// Mutably borrows the shared [Data].
+    ///
+    /// # Panics
+    /// Panics if the data are currently borrowed.
+    pub fn borrow_mut(&self) -> RefMut<'_, Data> {
+        self.0.borrow_mut()
+    }
+
+    /// Increments the counter of the shared [Data] (notice that `&self` is enough)
+    pub fn increment(&self) {
+        self.borrow_mut().0 += 1;
+    }
+
+    /// Returns current value of the counter of the shared [Data]
+    pub fn count(&self) -> usize {
+        self.borrow().0
+    }
 }
 
 #[cfg(test)]
@@ -57,6 +119,7 @@ mod tests {
             stack_shared: &stack_data,
             heap_owned: Data::default().into(),
             heap_shared: rc_data.clone(),
+            cow: Cow::Borrowed(&stack_data),
         });
 
         let box2 = Box::new(Container {
@@ -64,6 +127,7 @@ mod tests {
             stack_shared: &stack_data,
             heap_owned: Data::default().into(),
             heap_shared: rc_data.clone(),
+            cow: Cow::Borrowed(&stack_data),
         });
 
         // Clone both containers
@@ -93,4 +157,89 @@ mod tests {
         assert_eq!(rc_data.0, 0);
         assert_eq!(clone2.heap_shared.0, 0);
     }
+
+    #[test]
+    fn reference_counts() {
+        let stack_data = Data::default();
+
+        let container = Container {
+            owned: Data::default(),
+            stack_shared: &stack_data,
+            heap_owned: Data::default().into(),
+            heap_shared: Rc::new(Data::default()),
+            cow: Cow::Borrowed(&stack_data),
+        };
+        assert_eq!(strong_weak_counts(&container), (1, 0));
+
+        // Cloning the container increments the strong count but does not clone the data
+        let clone = container.clone();
+        assert_eq!(strong_weak_counts(&container), (2, 0));
+        assert_eq!(strong_weak_counts(&clone), (2, 0));
+        assert_eq!(clone.heap_shared.0, 0);
+
+        // Weak references are counted separately and don't keep the data alive
+        let weak = Rc::downgrade(&container.heap_shared);
+        assert_eq!(strong_weak_counts(&clone), (2, 1));
+
+        drop(container);
+        assert_eq!(strong_weak_counts(&clone), (1, 1));
+
+        drop(clone);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn clone_on_write() {
+        let stack_data = Data::default();
+
+        let container = Container {
+            owned: Data::default(),
+            stack_shared: &stack_data,
+            heap_owned: Data::default().into(),
+            heap_shared: Rc::new(Data::default()),
+            cow: Cow::Borrowed(&stack_data),
+        };
+
+        // Borrowed data behind `Cow` are *not* cloned, the clone borrows the same data (like `&`)
+        let mut clone = container.clone();
+        assert!(matches!(clone.cow, Cow::Borrowed(_)));
+        assert_eq!(clone.cow.0, 0);
+
+        // Requesting mutable access clones the data (like `Box`) and the `Cow` becomes owned
+        clone.cow.to_mut();
+        assert!(matches!(clone.cow, Cow::Owned(_)));
+        assert_eq!(clone.cow.0, 1);
+
+        // The original data and container are unaffected
+        assert_eq!(stack_data.0, 0);
+        assert_eq!(container.cow.0, 0);
+
+        // Once owned, cloning the container deep copies the data as well
+        let clone = clone.clone();
+        assert_eq!(clone.cow.0, 2);
+    }
+
+    #[test]
+    fn shared_interior_mutability() {
+        let shared = Shared::default();
+        let clone = shared.clone();
+
+        // Both clones point to the same cell, so a mutation is visible through either of them
+        clone.increment();
+        assert_eq!(shared.count(), 1);
+
+        shared.borrow_mut().0 += 1;
+        assert_eq!(clone.count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn shared_double_mutable_borrow() {
+        let shared = Shared::default();
+        let clone = shared.clone();
+
+        // This compiles just fine but violates the borrowing rules at runtime
+        let _first = shared.borrow_mut();
+        let _second = clone.borrow_mut();
+    }
 }
@@ -1,9 +1,15 @@
 //! Example of [Fast inverse square root](https://en.wikipedia.org/wiki/Fast_inverse_square_root).
+use core::cmp::max;
+use core::fmt;
+use core::iter::Sum;
 use derive_more::{Add, Mul};
-use std::cmp::max;
 
 const THREE_HALFS: f32 = 1.5;
 
+/// Magic constant for the initial cube root estimate taken from *fdlibm*'s `cbrtf`:
+/// `B1 = (127 - 127.0 / 3 - 0.03306235651) * 2^23`
+const CBRT_MAGIC: u32 = 0x2a5119f2;
+
 /// Approximates the inverse square root of given number.
 ///
 /// Note that this a port of the original *C* implementation and as such it is generally *unsafe*.
@@ -30,6 +36,30 @@ pub fn rsqrt(number: f32) -> f32 {
     y
 }
 
+#[cfg(feature = "libm")]
+#[inline(always)]
+fn sqrt(x: f32) -> f32 {
+    libm::sqrtf(x)
+}
+
+#[cfg(not(feature = "libm"))]
+#[inline(always)]
+fn sqrt(x: f32) -> f32 {
+    x.sqrt()
+}
+
+#[cfg(feature = "libm")]
+#[inline(always)]
+fn sqrt_f64(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+#[cfg(not(feature = "libm"))]
+#[inline(always)]
+fn sqrt_f64(x: f64) -> f64 {
+    x.sqrt()
+}
+
 /// Thin wrapper around [f32] with additional semantics that the values can only be positive floats
 /// and excluding infinity and nan.
 ///
@@ -84,11 +114,54 @@ impl PositiveFloat {
         self.0
     }
 
+    /// Calculates the square root of given number
+    ///
+    /// The square root of a positive normal float is again a positive normal float, so the result
+    /// is a valid [PositiveFloat]. In particular, this recovers (the absolute value of) `x` from
+    /// [PositiveFloat::from_square].
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::rsqrt::PositiveFloat;
+    ///
+    /// assert_eq!(PositiveFloat::from_square(3.0).sqrt().inner(), 3.0);
+    /// assert_eq!(PositiveFloat::from_square(-3.0).sqrt().inner(), 3.0);
+    /// ```
+    #[inline]
+    pub fn sqrt(&self) -> Self {
+        Self(sqrt(self.0))
+    }
+
+    /// Sums all the numbers in given iterator using the derived [Add](core::ops::Add)
+    ///
+    /// Because [PositiveFloat] has no zero to start the summation from, this returns [None] if the
+    /// iterator is empty. The same is available via the [Sum] impl for
+    /// `Option<PositiveFloat>`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::rsqrt::PositiveFloat;
+    ///
+    /// let xs = [1.0, 2.0, 3.5].map(|x| PositiveFloat::new(x).expect("positive number"));
+    ///
+    /// assert_eq!(PositiveFloat::sum(xs).map(|x| x.inner()), Some(6.5));
+    /// assert_eq!(xs.iter().copied().sum::<Option<PositiveFloat>>(), PositiveFloat::sum(xs));
+    /// assert_eq!(PositiveFloat::sum([]), None);
+    /// ```
+    pub fn sum<I: IntoIterator<Item = Self>>(iter: I) -> Option<Self> {
+        iter.into_iter().reduce(|acc, x| acc + x)
+    }
+
     /// Calculates the inverse square root of given number
+    ///
+    /// This is the exact (reference) implementation which is used to validate
+    /// [PositiveFloat::fast_rsqrt]. With the `libm` feature enabled, the square root is computed
+    /// by [libm](https://crates.io/crates/libm) instead of [f32::sqrt] which is not available
+    /// without `std` (e.g. in embedded contexts).
     #[inline]
     pub fn rsqrt(&self) -> Self {
         // This is safe because `x -> 1 / sqrt(x)` is known to be positive
-        Self(self.0.sqrt().recip())
+        Self(sqrt(self.0).recip())
     }
 
     /// Approximates the inverse square root of given number.
@@ -100,13 +173,20 @@ impl PositiveFloat {
     /// Constant generic parameter `ITERS` determines the number of iterations of the Newton's
     /// method used to find the approximation. Note that despite the fact that it is [usize], the
     /// implementation executes at least one iteration even if it is set to `0`.
+    #[inline]
     pub fn fast_rsqrt<const ITERS: usize>(&self) -> Self {
+        self.fast_rsqrt_iters(ITERS)
+    }
+
+    /// Version of [PositiveFloat::fast_rsqrt] with the number of Newton's iterations `iters` given
+    /// at runtime instead of as a constant generic parameter.
+    pub fn fast_rsqrt_iters(&self, iters: usize) -> Self {
         let x2 = self.0 * 0.5;
         let i = self.0.to_bits();
         let mut y = f32::from_bits(0x5f3759df - (i >> 1));
 
         // Newton's method (at least one iteration)
-        for _ in 0..max(ITERS, 1) {
+        for _ in 0..max(iters, 1) {
             y *= THREE_HALFS - (x2 * y * y);
         }
 
@@ -115,6 +195,101 @@ impl PositiveFloat {
     }
 }
 
+impl PositiveFloat {
+    /// Approximates the cube root of given number using the same kind of bit hack as
+    /// [PositiveFloat::fast_rsqrt].
+    ///
+    /// Dividing the bit representation of a float by 3 approximately divides its exponent by 3
+    /// (i.e. takes the cube root of its magnitude). Adding the magic constant `0x2a5119f2` then
+    /// re-biases the exponent and corrects the mantissa so that the initial estimate is within a
+    /// few percent of the exact value.
+    ///
+    /// The estimate is then refined by `ITERS` iterations (at least one) of the Newton's method
+    /// for `y^3 - x = 0`, i.e. `y = (2y + x / y^2) / 3`.
+    ///
+    /// Just like with the inverse square root, the construction of [PositiveFloat] guarantees
+    /// that this is only called on valid inputs and the result is again positive.
+    pub fn fast_cbrt<const ITERS: usize>(&self) -> Self {
+        let x = self.0;
+        let mut y = f32::from_bits(x.to_bits() / 3 + CBRT_MAGIC);
+
+        // Newton's method (at least one iteration)
+        for _ in 0..max(ITERS, 1) {
+            y = (2. * y + x / (y * y)) / 3.;
+        }
+
+        // This is safe because `x -> cbrt(x)` is known to be positive for positive `x`
+        Self(y)
+    }
+}
+
+impl Sum<PositiveFloat> for Option<PositiveFloat> {
+    #[inline]
+    fn sum<I: Iterator<Item = PositiveFloat>>(iter: I) -> Self {
+        PositiveFloat::sum(iter)
+    }
+}
+
+/// [PositiveFloat] is displayed just like the inner [f32] (including any formatting options).
+///
+/// ```
+/// use rust_examples::rsqrt::PositiveFloat;
+///
+/// let x = PositiveFloat::new(4.2).expect("positive normal float");
+/// assert_eq!(format!("{:.2}", x), "4.20");
+/// ```
+impl fmt::Display for PositiveFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// [PositiveFloat] is formatted in the scientific notation just like the inner [f32].
+impl fmt::LowerExp for PositiveFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerExp::fmt(&self.0, f)
+    }
+}
+
+/// Scans `samples` evenly spaced inputs from the range `[lo, hi]` and returns the one for which
+/// [`fast_rsqrt_iters(iters)`](PositiveFloat::fast_rsqrt_iters) has the maximum *relative* error
+/// together with that error.
+///
+/// This characterizes where the bit hack is least accurate. For instance, with a single Newton's
+/// iteration the relative error is known to stay below `0.2%`.
+///
+/// # Panics
+/// This function panics if `samples` is zero or if `lo` or `hi` is not a valid [PositiveFloat].
+pub fn worst_case_input(lo: f32, hi: f32, samples: usize, iters: usize) -> (f32, f64) {
+    assert!(samples > 0, "At least one sample is required");
+
+    let lo = PositiveFloat::new(lo).expect("lo must be a positive normal float");
+    let hi = PositiveFloat::new(hi).expect("hi must be a positive normal float");
+
+    let step = if samples > 1 {
+        (hi.0 - lo.0) / (samples - 1) as f32
+    } else {
+        0.
+    };
+
+    (0..samples)
+        .map(|i| lo.0 + step * i as f32)
+        // Points in `[lo, hi]` are positive and normal, so this never filters out anything
+        .filter_map(PositiveFloat::new)
+        .map(|x| {
+            let exact = sqrt_f64(x.0 as f64).recip();
+            let approx = x.fast_rsqrt_iters(iters).0 as f64;
+            (x.0, ((approx - exact) / exact).abs())
+        })
+        .fold((lo.0, 0.), |worst, candidate| {
+            if candidate.1 > worst.1 {
+                candidate
+            } else {
+                worst
+            }
+        })
+}
+
 /// Type alias for 3D vector represented as 3-tuple of [f32]
 pub type Vec3D = (f32, f32, f32);
 
@@ -155,6 +330,100 @@ impl FastNormalize for Vec3D {
     }
 }
 
+/// In-place version of [`FastNormalize::normalize`] for [Vec3D] which overwrites `v` with its
+/// normalization.
+///
+/// Returns `false` and leaves `v` unchanged if any component is not a normal float.
+///
+/// # Example
+/// ```
+/// use rust_examples::rsqrt::normalize_in_place;
+///
+/// let mut v = (3., 4., 12.);
+/// assert!(normalize_in_place(&mut v));
+///
+/// let mut v = (1., f32::NAN, 1.);
+/// assert!(!normalize_in_place(&mut v));
+/// ```
+pub fn normalize_in_place(v: &mut Vec3D) -> bool {
+    match v.normalize() {
+        Some(n) => {
+            *v = n;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Computes the dot (scalar) product of two [Vec3D]s.
+///
+/// ```
+/// use rust_examples::rsqrt::dot;
+///
+/// assert_eq!(dot((1., 2., 3.), (4., -5., 6.)), 12.);
+/// ```
+#[inline]
+pub fn dot(a: Vec3D, b: Vec3D) -> f32 {
+    a.0 * b.0 + a.1 * b.1 + a.2 * b.2
+}
+
+/// Computes the cross (vector) product `a x b` of two [Vec3D]s, i.e. a vector which is orthogonal
+/// to both `a` and `b` (following the right-hand rule).
+#[inline]
+pub fn cross(a: Vec3D, b: Vec3D) -> Vec3D {
+    (
+        a.1 * b.2 - a.2 * b.1,
+        a.2 * b.0 - a.0 * b.2,
+        a.0 * b.1 - a.1 * b.0,
+    )
+}
+
+/// Type alias for 2D vector represented as 2-tuple of [f32]
+pub type Vec2D = (f32, f32);
+
+/// This implementation of [FastNormalize] is the 2D analogy of the one for [Vec3D], i.e. it uses
+/// a [`fast_rsqrt<1>`](PositiveFloat::fast_rsqrt) to compute the inverse square root of
+/// `x^2 + y^2` and returns [None] if any of the components is not a *normal* float.
+impl FastNormalize for Vec2D {
+    type NormVec = Option<Vec2D>;
+
+    fn normalize(&self) -> Self::NormVec {
+        let &(x, y) = self;
+
+        if !x.is_normal() || !y.is_normal() {
+            return None;
+        }
+
+        let squares_sum = PositiveFloat::from_square(x) + PositiveFloat::from_square(y);
+
+        let recip_norm = squares_sum.fast_rsqrt::<1>().inner();
+
+        Some((x * recip_norm, y * recip_norm))
+    }
+}
+
+/// Normalizes an `N`-dimensional vector `v` using [PositiveFloat::fast_rsqrt_iters] with `iters`
+/// Newton's iterations, so that the callers can trade accuracy for speed at runtime.
+///
+/// Contrary to the [FastNormalize] implementations above, individual components can be zero. The
+/// only requirement is that the sum of squares of the components is a valid [PositiveFloat] (i.e.
+/// it's not zero, subnormal, infinite nor nan), otherwise [None] is returned.
+///
+/// # Example
+/// ```
+/// use rust_examples::rsqrt::normalize_iters;
+///
+/// let [x, y, z, w] = normalize_iters([1., 0., 2., -2.], 2).expect("non-zero vector");
+/// assert!((x * x + y * y + z * z + w * w - 1.).abs() < 1e-5);
+///
+/// assert_eq!(normalize_iters([0.; 4], 2), None);
+/// ```
+pub fn normalize_iters<const N: usize>(v: [f32; N], iters: usize) -> Option<[f32; N]> {
+    let squares_sum = PositiveFloat::new(v.iter().map(|x| x * x).sum())?;
+    let recip_norm = squares_sum.fast_rsqrt_iters(iters).inner();
+    Some(v.map(|x| x * recip_norm))
+}
+
 /// Type that represents *normal* [f32] numbers. This excludes numbers that are
 ///  - NaN
 ///  - Infinite
@@ -178,6 +447,30 @@ impl Float {
     pub fn square(&self) -> PositiveFloat {
         PositiveFloat::from_square(self.0)
     }
+
+    /// Converts this [Float] into a [PositiveFloat] if it's sign positive, otherwise returns
+    /// `None`.
+    ///
+    /// A [Float] is already normal by construction, so the sign is the only thing left to check.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_examples::rsqrt::Float;
+    ///
+    /// let x = Float::new(4.2).expect("normal float");
+    /// assert_eq!(x.try_into_positive().map(|x| x.inner()), Some(4.2));
+    ///
+    /// let x = Float::new(-4.2).expect("normal float");
+    /// assert_eq!(x.try_into_positive(), None);
+    /// ```
+    #[inline]
+    pub fn try_into_positive(&self) -> Option<PositiveFloat> {
+        if self.0.is_sign_positive() {
+            Some(PositiveFloat(self.0))
+        } else {
+            None
+        }
+    }
 }
 
 /// Optimized implementation of [FastNormalize] for 3D vector of [Float]s.
@@ -205,12 +498,29 @@ impl FastNormalize for (Float, Float, Float) {
 mod tests {
     use super::*;
 
-    use crate::approx;
-    use quickcheck::TestResult;
+    use crate::{approx, assert_approx_slice};
+    use quickcheck::{Arbitrary, Gen, TestResult};
     use rstest::*;
+    use std::f32::consts::FRAC_1_SQRT_2;
 
     const EPS: f64 = 0.005;
 
+    /// Generates only valid (normal and sign positive) [PositiveFloat]s so that properties can
+    /// take these directly and don't have to discard any generated inputs.
+    impl Arbitrary for PositiveFloat {
+        fn arbitrary(g: &mut Gen) -> Self {
+            loop {
+                if let Some(x) = PositiveFloat::new(f32::arbitrary(g).abs()) {
+                    return x;
+                }
+            }
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            Box::new(self.0.shrink().filter_map(PositiveFloat::new))
+        }
+    }
+
     #[rstest]
     #[case::nan(f32::NAN, None)]
     #[case::inf(f32::INFINITY, None)]
@@ -250,16 +560,107 @@ mod tests {
     }
 
     #[quickcheck]
-    fn safe_rsqrt_approximates_inverse_square_root(number: f32) -> TestResult {
-        if let Some(number) = PositiveFloat::new(number) {
-            let estimate = number.fast_rsqrt::<1>().inner();
-            let target = number.rsqrt().inner();
-            let close_approx = approx!(estimate, target; EPS);
+    fn fast_rsqrt_close(x: PositiveFloat) -> bool {
+        approx!(x.fast_rsqrt::<1>().inner(), x.rsqrt().inner(); EPS)
+    }
 
-            TestResult::from_bool(close_approx)
-        } else {
-            TestResult::discard()
-        }
+    #[rstest]
+    #[case(1.0)]
+    #[case(8.0)]
+    #[case(0.15625)]
+    #[case(27.0)]
+    #[case(1234.5)]
+    #[case(1e-20)]
+    #[case(3e30)]
+    fn fast_cube_root(#[case] number: f32) {
+        let x = PositiveFloat::new(number).expect("positive number");
+        let estimate = x.fast_cbrt::<2>().inner();
+        let target = number.cbrt();
+        assert!(
+            approx!(estimate / target, 1.0; 1e-4),
+            "{} is not close to cbrt({}) = {}",
+            estimate,
+            number,
+            target
+        );
+    }
+
+    #[rstest]
+    #[case::positive(4.2, Some(4.2))]
+    #[case::small(f32::MIN_POSITIVE, Some(f32::MIN_POSITIVE))]
+    #[case::max(f32::MAX, Some(f32::MAX))]
+    #[case::negative(-4.2, None)]
+    #[case::negative_max(f32::MIN, None)]
+    fn float_into_positive(#[case] number: f32, #[case] expected: Option<f32>) {
+        let x = Float::new(number).expect("normal float");
+        assert_eq!(x.try_into_positive().map(|x| x.inner()), expected);
+    }
+
+    #[rstest]
+    fn sqrt_recovers_from_square() {
+        let x = PositiveFloat::from_square(3.0).sqrt().inner();
+        assert!(approx!(x, 3.0; 1e-6), "{} is not close to 3", x);
+    }
+
+    #[rstest]
+    fn sum_positive_floats() {
+        let xs = [1.5, 2.0, 0.25].map(|x| PositiveFloat::new(x).expect("positive number"));
+        let expected = Some(PositiveFloat(3.75));
+
+        assert_eq!(PositiveFloat::sum(xs), expected);
+        assert_eq!(xs.iter().copied().sum::<Option<PositiveFloat>>(), expected);
+    }
+
+    #[rstest]
+    fn sum_empty() {
+        assert_eq!(PositiveFloat::sum(core::iter::empty()), None);
+        assert_eq!(core::iter::empty().sum::<Option<PositiveFloat>>(), None);
+    }
+
+    #[quickcheck]
+    fn sqrt_squared_recovers_original(x: PositiveFloat) -> bool {
+        // Square in f64 so that the square root of large floats does not overflow
+        let squared = x.sqrt().inner() as f64 * x.sqrt().inner() as f64;
+        approx!(squared / x.inner() as f64, 1.0; 1e-6)
+    }
+
+    #[cfg(feature = "libm")]
+    #[quickcheck]
+    fn libm_rsqrt_matches_std(x: PositiveFloat) -> bool {
+        x.rsqrt().inner() == x.inner().sqrt().recip()
+    }
+
+    #[cfg(feature = "libm")]
+    #[quickcheck]
+    fn libm_rsqrt_approximates_fast_rsqrt(x: PositiveFloat) -> bool {
+        let (exact, fast) = (x.rsqrt().inner(), x.fast_rsqrt::<2>().inner());
+        approx!(fast / exact, 1.0; 1e-4)
+    }
+
+    #[rstest]
+    #[case(1.0)]
+    #[case(4.2)]
+    #[case(1234.5678)]
+    #[case(1e-20)]
+    #[case(f32::MAX)]
+    fn format_like_f32(#[case] number: f32) {
+        let x = PositiveFloat::new(number).expect("positive number");
+        assert_eq!(format!("{}", x), format!("{}", number));
+        assert_eq!(format!("{:.3}", x), format!("{:.3}", number));
+        assert_eq!(format!("{:>12}", x), format!("{:>12}", number));
+        assert_eq!(format!("{:e}", x), format!("{:e}", number));
+        assert_eq!(format!("{:.2e}", x), format!("{:.2e}", number));
+    }
+
+    #[rstest]
+    fn worst_case_single_iteration() {
+        let (x, err) = worst_case_input(1.0, 4.0, 10_000, 1);
+        assert!((1.0..=4.0).contains(&x), "{} is not within [1, 4]", x);
+        assert!(err > 0. && err < 0.002, "unexpected relative error {}", err);
+
+        // More iterations of the Newton's method only improve the approximation
+        let (_, err2) = worst_case_input(1.0, 4.0, 10_000, 2);
+        assert!(err2 < err);
     }
 
     #[rstest]
@@ -275,10 +676,96 @@ mod tests {
 
         assert_eq!(v_norm.is_some(), some);
 
-        if let Some((x, y, z)) = v_norm {
+        if let Some((x_norm, y_norm, z_norm)) = v_norm {
             // Compute and check conventional norm
+            let norm = (x_norm * x_norm + y_norm * y_norm + z_norm * z_norm).sqrt();
+
+            assert!(
+                approx!(norm, 1.0; EPS),
+                "Norm should be approx. one, got: {}",
+                norm
+            );
+
+            // Compare with the exact normalization
+            let (x, y, z) = (x as f64, y as f64, z as f64);
             let norm = (x * x + y * y + z * z).sqrt();
+            assert_approx_slice!(
+                [x_norm as f64, y_norm as f64, z_norm as f64],
+                [x / norm, y / norm, z / norm];
+                EPS
+            );
+        }
+    }
+
+    #[rstest]
+    #[case((1., 2., 3.))]
+    #[case((4.2, -1., -1.))]
+    #[case((-3., 4., 12.))]
+    fn fast_normalization_in_place(#[case] v: Vec3D) {
+        let mut v_norm = v;
 
+        assert!(normalize_in_place(&mut v_norm));
+        assert_eq!(Some(v_norm), v.normalize());
+
+        let (x, y, z) = v_norm;
+        let norm = (x * x + y * y + z * z).sqrt();
+        assert!(
+            approx!(norm, 1.0; EPS),
+            "Norm should be approx. one, got: {}",
+            norm
+        );
+    }
+
+    #[rstest]
+    #[case((1., f32::INFINITY, 2.))]
+    #[case((f32::NEG_INFINITY, 1., 2.))]
+    #[case((1., 2., 0.))]
+    fn fast_normalization_in_place_invalid(#[case] v: Vec3D) {
+        let mut v_norm = v;
+        assert!(!normalize_in_place(&mut v_norm));
+        assert_eq!(v_norm, v);
+    }
+
+    #[rstest]
+    #[case((1., 0., 0.), (0., 1., 0.))]
+    #[case((1., 2., 3.), (-2., 1., 0.))]
+    #[case((0., 0., 0.), (4., 2., 1.))]
+    fn dot_orthogonal(#[case] a: Vec3D, #[case] b: Vec3D) {
+        assert_eq!(dot(a, b), 0.);
+        assert_eq!(dot(b, a), 0.);
+    }
+
+    #[rstest]
+    fn cross_product() {
+        let (x, y, z) = ((1., 0., 0.), (0., 1., 0.), (0., 0., 1.));
+        assert_eq!(cross(x, y), z);
+        assert_eq!(cross(y, z), x);
+        assert_eq!(cross(z, x), y);
+        assert_eq!(cross(y, x), (0., 0., -1.));
+
+        // Cross product is orthogonal to both operands
+        let (a, b) = ((1., 2., 3.), (4., -5., 6.));
+        let c = cross(a, b);
+        assert_eq!(dot(a, c), 0.);
+        assert_eq!(dot(b, c), 0.);
+    }
+
+    #[rstest]
+    #[case(3.0, 4.0, Some((0.6, 0.8)))]
+    #[case(-1.0, 1.0, Some((-FRAC_1_SQRT_2, FRAC_1_SQRT_2)))]
+    #[case(0.0, 0.0, None)]
+    #[case(1.0, 0.0, None)]
+    #[case(f32::INFINITY, 1.0, None)]
+    #[case(1.0, f32::NAN, None)]
+    fn fast_normalization_2d(#[case] x: f32, #[case] y: f32, #[case] expected: Option<Vec2D>) {
+        let v_norm = (x, y).normalize();
+
+        assert_eq!(v_norm.is_some(), expected.is_some());
+
+        if let (Some((x, y)), Some((ex, ey))) = (v_norm, expected) {
+            assert_approx_slice!([x as f64, y as f64], [ex as f64, ey as f64]; EPS);
+
+            let norm = (x * x + y * y).sqrt();
             assert!(
                 approx!(norm, 1.0; EPS),
                 "Norm should be approx. one, got: {}",
@@ -287,6 +774,35 @@ mod tests {
         }
     }
 
+    #[rstest]
+    fn normalize_more_iterations() {
+        let v = [3.2, -1.5, 0., 42., 0.007];
+
+        let norm_error = |iters| {
+            let normalized = normalize_iters(v, iters).expect("non-zero vector");
+            let norm = normalized
+                .iter()
+                .map(|&x| x as f64 * x as f64)
+                .sum::<f64>()
+                .sqrt();
+            (norm - 1.).abs()
+        };
+
+        let errors = [1, 2, 3].map(norm_error);
+        assert!(errors[0] < EPS, "norm error {} is too large", errors[0]);
+        assert!(errors[1] < errors[0], "{:?} are not decreasing", errors);
+        assert!(errors[2] <= errors[1], "{:?} are not decreasing", errors);
+    }
+
+    #[rstest]
+    #[case::zero([0.; 3])]
+    #[case::nan([1., f32::NAN, 1.])]
+    #[case::inf([1., f32::INFINITY, 1.])]
+    #[case::overflow([f32::MAX, 1., 1.])]
+    fn normalize_invalid(#[case] v: [f32; 3]) {
+        assert_eq!(normalize_iters(v, 2), None);
+    }
+
     #[rstest]
     #[case(Float(1.0), Float(1.0), Float(1.0))]
     #[case(Float(1.0), Float(2.0), Float(3.0))]
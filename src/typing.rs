@@ -6,28 +6,30 @@
 //! floats cannot be compared (e.g. *nan*).
 //!
 //! However, we can restrict our comparison to *positive floats*. The memory representation of each
-//! positive float in IEEE standard format can be interpreted as [u32]. Integer representations of
-//! positive floats are then *monotonic* and thus have efficient [Ordering].
+//! positive float in IEEE standard format can be interpreted as [u64] (see [f64::to_bits]).
+//! Integer representations of positive floats (excluding *nan*) are then *monotonic* and thus
+//! have efficient [Ordering].
 //!
-//! The three examples presented here then address the two remaining problems:
-//!  1. How to ensure that given [f64] is positive
-//!  1. How to *safely* compare floats when [f64::to_int_unchecked] is `unsafe`
+//! All three examples presented here compare the raw bits, so they agree on all valid inputs. They
+//! differ in how they address the remaining problems:
+//!  1. How to ensure that given [f64] is positive (and not a *nan*)
+//!  1. How to avoid paying for this check on each and every comparison
 //!
 //! Note that one would typically realize these as implementations of [PartialOrd] or [Ord] but we
-//! keep it simple and implement the comparison as plain function.
-//!
-//! Also note that `std` actually defines a safe memory interpretation [f64::to_bits] so this
-//! example is somewhat artificial.
+//! keep it simple and implement the comparison as plain function. For the realization via [Ord]
+//! see the implementation for [Positive].
 //!
 //! Last few examples describe the [top](https://en.wikipedia.org/wiki/Top_type) and
 //! [bottom](https://en.wikipedia.org/wiki/Bottom_type) type realized in Rust's type system.
-use std::cmp::Ordering;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
 
 /// Naive *positive* [f64] comparison function.
 ///
-/// This implementation first checks both arguments whether they are positive and *panics*
-/// otherwise. After this check it is safe to call [f64::to_int_unchecked] and do the comparison on
-/// [u32]s;
+/// This implementation first checks both arguments whether they are positive (and not a *nan*) and
+/// *panics* otherwise. After this check the comparison can be done on the raw bits as [u64]s.
 ///
 /// # Pros
 /// 1. Clients don't have to check for anything on the call side
@@ -40,15 +42,11 @@ use std::cmp::Ordering;
 /// 1. Typical protection against #1 will be adding the same check and thus duplicating the
 ///    code
 pub fn cmp_f64(a: f64, b: f64) -> Ordering {
-    if !a.is_sign_positive() || !b.is_sign_positive() {
+    if !a.is_sign_positive() || !b.is_sign_positive() || a.is_nan() || b.is_nan() {
         panic!("Both a and b must be positive reals");
     }
 
-    unsafe {
-        let a: u32 = a.to_int_unchecked();
-        let b: u32 = b.to_int_unchecked();
-        a.cmp(&b)
-    }
+    a.to_bits().cmp(&b.to_bits())
 }
 
 /// Improved comparison function for *positive* [f64]s.
@@ -66,17 +64,13 @@ pub fn cmp_f64(a: f64, b: f64) -> Ordering {
 /// 1. Clients must handle the [`Option<Ordering>`](Option) and it might not integrate well with
 ///    other standard APIs
 /// 1. For performance-critical applications it's quite unfortunate that this version double checks
-///    `a` and `b` with an `if` to make sure the `unsafe` block is sound
+///    `a` and `b` with an `if` to make sure the comparison of the raw bits is meaningful
 pub fn better_cmp_f64(a: f64, b: f64) -> Option<Ordering> {
-    if !a.is_sign_positive() || !b.is_sign_positive() {
+    if !a.is_sign_positive() || !b.is_sign_positive() || a.is_nan() || b.is_nan() {
         return None;
     }
 
-    unsafe {
-        let a: u32 = a.to_int_unchecked();
-        let b: u32 = b.to_int_unchecked();
-        Some(a.cmp(&b))
-    }
+    Some(a.to_bits().cmp(&b.to_bits()))
 }
 
 /// Opaque wrapper around [f64] which adds static semantics that the float has positive value.
@@ -100,10 +94,20 @@ pub fn better_cmp_f64(a: f64, b: f64) -> Option<Ordering> {
 ///
 /// struct Positive<F: Float>(F);
 /// ```
-#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+/// which is realized by `PositiveNum` (requires the `num-traits` feature).
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Positive(f64);
 
 impl Positive {
+    /// The multiplicative identity `1`
+    pub const ONE: Self = unsafe { Self::new_unchecked(1.) };
+
+    /// Euler's number `e`
+    pub const E: Self = unsafe { Self::new_unchecked(core::f64::consts::E) };
+
+    /// Archimedes' constant `π`
+    pub const PI: Self = unsafe { Self::new_unchecked(core::f64::consts::PI) };
+
     /// This forces clients to always check if it's ok. One cannot initialize a tuple struct which
     /// contains private fields.
     ///
@@ -119,13 +123,43 @@ impl Positive {
     ///
     /// assert_eq!(Positive::new(-24.), None)
     /// ```
+    ///
+    /// Note that a *nan* is rejected as well, even though it might have a positive sign bit.
+    /// ```
+    /// use rust_examples::typing::Positive;
+    ///
+    /// assert_eq!(Positive::new(f64::NAN), None)
+    /// ```
     pub fn new(number: f64) -> Option<Self> {
-        if !number.is_sign_positive() {
+        if !number.is_sign_positive() || number.is_nan() {
             return None;
         }
         Some(Self(number))
     }
 
+    /// Creates a [Positive] number without checking the invariant.
+    ///
+    /// Being a `const fn`, this can be used to define constants (such as [Positive::PI]) without
+    /// any runtime check. The invariant is only checked by a debug assertion (which in a `const`
+    /// context fails the compilation).
+    ///
+    /// ```
+    /// use rust_examples::typing::Positive;
+    ///
+    /// const TWO: Positive = unsafe { Positive::new_unchecked(2.) };
+    /// assert_eq!(TWO.get(), 2.);
+    /// ```
+    ///
+    /// # Safety
+    /// The caller must guarantee that `number` is sign positive and not a *nan* (i.e. that
+    /// [Positive::new] would return `Some`), otherwise e.g. [safe_cmp_f64] yields meaningless
+    /// results.
+    #[inline]
+    pub const unsafe fn new_unchecked(number: f64) -> Self {
+        debug_assert!(number.is_sign_positive() && !number.is_nan());
+        Self(number)
+    }
+
     /// Interprets [Positive] as an [u32]
     ///
     /// Note that this is not OOP, one can call [Positive::as_u32] as an ordinary function:
@@ -137,12 +171,337 @@ impl Positive {
     /// assert_eq!(int, 42);
     /// ```
     ///
+    /// The fractional part is truncated, so e.g. `4.2` is interpreted as `4` (see the
+    /// [TryFrom](#impl-TryFrom<Positive>-for-u32) implementation for a safe and exact alternative).
+    ///
     /// # Safety
-    /// The safety is guaranteed by the construction of [Positive] instances via [Positive::new].
+    /// The caller must guarantee that the truncated number is representable as [u32], i.e. that it
+    /// is at most [u32::MAX] (and in particular not an infinity). This is **not** guaranteed by the
+    /// construction of [Positive] alone (see [f64::to_int_unchecked]).
     #[inline(always)]
     pub unsafe fn as_u32(&self) -> u32 {
         self.0.to_int_unchecked::<u32>()
     }
+
+    /// Retrieves the inner [f64] value
+    ///
+    /// ```
+    /// use rust_examples::typing::Positive;
+    ///
+    /// let pos = Positive::new(42.).expect("positive number");
+    /// assert_eq!(pos.get(), 42.);
+    /// ```
+    #[inline]
+    pub fn get(&self) -> f64 {
+        self.0
+    }
+
+    /// Negates this [Positive] number.
+    ///
+    /// The result is no longer positive, so it can't be a [Positive] and the invariant is instead
+    /// captured by returning a [Signed] float (see [NegatePositiveTest]).
+    ///
+    /// ```
+    /// use rust_examples::typing::Positive;
+    ///
+    /// let pos = Positive::new(42.).expect("positive number");
+    /// assert_eq!(pos.neg().get(), -42.);
+    /// ```
+    #[inline]
+    pub fn neg(&self) -> Signed {
+        Signed(-self.0)
+    }
+
+    /// Subtracts `rhs` from this [Positive] number, saturating at [f64::MIN_POSITIVE].
+    ///
+    /// The true difference of two positive numbers can be zero, negative or subnormal, in which
+    /// case the result is clamped to the smallest positive normal [f64] (which is the *saturation
+    /// floor*) so that the result is always a valid [Positive].
+    ///
+    /// ```
+    /// use rust_examples::typing::Positive;
+    ///
+    /// let x = Positive::new(3.).expect("positive number");
+    /// let y = Positive::new(1.).expect("positive number");
+    ///
+    /// assert_eq!(x.saturating_sub(y).get(), 2.);
+    /// assert_eq!(y.saturating_sub(x).get(), f64::MIN_POSITIVE);
+    /// ```
+    #[inline]
+    pub fn saturating_sub(self, rhs: Positive) -> Positive {
+        let diff = self.0 - rhs.0;
+        // Note: `inf - inf` is NaN which also saturates
+        if diff > f64::MIN_POSITIVE {
+            Self(diff)
+        } else {
+            Self(f64::MIN_POSITIVE)
+        }
+    }
+
+    /// Computes the arithmetic mean of given [Positive] `values` or [None] if there are none.
+    ///
+    /// The naive sum loses the low-order bits of small values added to a large running total. This
+    /// implementation uses the [Kahan summation](https://en.wikipedia.org/wiki/Kahan_summation_algorithm)
+    /// which keeps track of these lost bits in a *compensation* term and feeds them back into the
+    /// next addition. Thanks to the positivity invariant, there's no catastrophic cancellation to
+    /// worry about and the mean is always a valid [Positive].
+    ///
+    /// Note that if the sum overflows, the result is [f64::INFINITY] (just as with the naive mean).
+    ///
+    /// ```
+    /// use rust_examples::typing::Positive;
+    ///
+    /// let values = [1., 2., 3.].map(|x| Positive::new(x).expect("positive number"));
+    /// assert_eq!(Positive::stable_mean(&values).map(|m| m.get()), Some(2.));
+    /// assert_eq!(Positive::stable_mean(&[]), None);
+    /// ```
+    pub fn stable_mean(values: &[Positive]) -> Option<Positive> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut sum = 0.;
+        let mut compensation = 0.;
+
+        for x in values {
+            let y = x.0 - compensation;
+            let t = sum + y;
+            if t.is_infinite() {
+                return Some(Self(f64::INFINITY));
+            }
+            // (t - sum) recovers the high-order part of y, subtracting y leaves the lost low bits
+            compensation = (t - sum) - y;
+            sum = t;
+        }
+
+        Some(Self(sum / values.len() as f64))
+    }
+
+    /// Wraps all the numbers in `xs` as [Positive]s if *every* one of them is positive (in the
+    /// sense of [Positive::new]), otherwise returns [None].
+    ///
+    /// This is a traversal of `xs` with the effect of [Positive::new] (see the `collect` module).
+    ///
+    /// ```
+    /// use rust_examples::typing::Positive;
+    ///
+    /// let values = Positive::try_from_slice(&[1., 4.2]).expect("all positive");
+    /// assert_eq!(values, vec![1., 4.2]);
+    ///
+    /// assert_eq!(Positive::try_from_slice(&[1., -1.]), None);
+    /// ```
+    pub fn try_from_slice(xs: &[f64]) -> Option<Vec<Positive>> {
+        xs.iter().copied().map(Self::new).collect()
+    }
+}
+
+/// Error returned when an [f64] which is not positive is converted to [Positive].
+///
+/// The rejected value is kept so that the caller can report or recover from it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NotPositive(pub f64);
+
+impl fmt::Display for NotPositive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not a positive float", self.0)
+    }
+}
+
+impl core::error::Error for NotPositive {}
+
+/// Fallible conversion which is an alternative to [Positive::new] that reports the reason of a
+/// failure via [NotPositive] instead of plain [None].
+///
+/// ```
+/// use rust_examples::typing::{NotPositive, Positive};
+///
+/// assert_eq!(Positive::try_from(-24.), Err(NotPositive(-24.)));
+/// ```
+impl TryFrom<f64> for Positive {
+    type Error = NotPositive;
+
+    #[inline]
+    fn try_from(number: f64) -> Result<Self, Self::Error> {
+        Self::new(number).ok_or(NotPositive(number))
+    }
+}
+
+/// Unwrapping [Positive] into an [f64] always succeeds, i.e. forgets the additional semantics.
+impl From<Positive> for f64 {
+    #[inline]
+    fn from(pos: Positive) -> Self {
+        pos.0
+    }
+}
+
+/// Error returned when a [Positive] number can't be converted to an [u32] without a loss of
+/// information.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NotU32(pub f64);
+
+impl fmt::Display for NotU32 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not representable as u32", self.0)
+    }
+}
+
+impl core::error::Error for NotU32 {}
+
+/// Safe alternative to [Positive::as_u32] which succeeds only if the number is exactly
+/// representable as an [u32] - i.e. it has no fractional part and is at most [u32::MAX].
+///
+/// Notice that there's no rounding, a fractional number is rejected rather than silently
+/// truncated.
+///
+/// ```
+/// use rust_examples::typing::{NotU32, Positive};
+///
+/// let pos = Positive::new(42.).expect("positive number");
+/// assert_eq!(u32::try_from(pos), Ok(42));
+///
+/// let pos = Positive::new(4.2).expect("positive number");
+/// assert_eq!(u32::try_from(pos), Err(NotU32(4.2)));
+/// ```
+impl TryFrom<Positive> for u32 {
+    type Error = NotU32;
+
+    #[inline]
+    fn try_from(pos: Positive) -> Result<Self, Self::Error> {
+        // Note: `f64::fract` requires `std`, the remainder is available in `core` as well
+        if pos.0 % 1. == 0. && pos.0 <= u32::MAX as f64 {
+            // Safety: The number is a non-negative integer within the range of `u32`
+            Ok(unsafe { pos.as_u32() })
+        } else {
+            Err(NotU32(pos.0))
+        }
+    }
+}
+
+/// Wrapper around [f64] which, contrary to [Positive], makes no assumptions about the sign of the
+/// value. It is the result of operations on [Positive] that can leave the positive reals, such as
+/// [Positive::neg].
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Signed(f64);
+
+impl Signed {
+    /// Retrieves the inner [f64] value
+    #[inline]
+    pub fn get(&self) -> f64 {
+        self.0
+    }
+}
+
+/// Since [Positive] can't be a *nan*, all its values are comparable and equality is reflexive.
+impl Eq for Positive {}
+
+impl PartialOrd for Positive {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Contrary to [f64], [Positive] is totally ordered because [Positive::new] excludes *nan*.
+///
+/// This makes it usable in APIs that require [Ord] such as sorting or [std::collections::BinaryHeap].
+/// For instance, a *min-heap* of positive floats can be realized with [std::cmp::Reverse]:
+/// ```
+/// use std::cmp::Reverse;
+/// use std::collections::BinaryHeap;
+/// use rust_examples::typing::Positive;
+///
+/// let mut heap = BinaryHeap::new();
+/// heap.push(Reverse(Positive::new(4.2).expect("positive number")));
+/// heap.push(Reverse(Positive::new(1.).expect("positive number")));
+///
+/// assert_eq!(heap.pop().map(|Reverse(pos)| pos.get()), Some(1.));
+/// ```
+impl Ord for Positive {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Positive {
+    /// Compares two [Positive] numbers up to a tolerance `eps`, i.e. numbers which are within `eps`
+    /// of each other are considered [Equal](Ordering::Equal).
+    ///
+    /// Note that this relation is **not** a total order, because the "approximately equal" part is
+    /// not transitive: with `eps = 1`, `1 ~ 2` and `2 ~ 3` but `1 < 3`. This is acceptable for
+    /// grouping noisy measurements, where differences below `eps` are meaningless anyway, but it
+    /// must not be used where a lawful [Ord] is required (see [sort_approx]).
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use rust_examples::typing::Positive;
+    ///
+    /// let x = Positive::new(1.).expect("positive number");
+    /// let y = Positive::new(1.0001).expect("positive number");
+    ///
+    /// assert_eq!(x.cmp_approx(&y, 1e-3), Ordering::Equal);
+    /// assert_eq!(x.cmp_approx(&y, 1e-6), Ordering::Less);
+    /// ```
+    pub fn cmp_approx(&self, other: &Positive, eps: f64) -> Ordering {
+        if (self.0 - other.0).abs() <= eps {
+            Ordering::Equal
+        } else {
+            self.cmp(other)
+        }
+    }
+}
+
+/// Sorts `values` in ascending order w.r.t. [Positive::cmp_approx] with tolerance `eps`.
+///
+/// The sort is *stable*, so values which are approximately equal keep their original relative
+/// order instead of being shuffled by noise below `eps`.
+///
+/// Since [Positive::cmp_approx] is not a total order, it can't be passed to [slice::sort_by]
+/// which is allowed to panic (or produce an unspecified order) for such comparators. Instead, this
+/// is a simple insertion sort which only relies on the comparison being consistent for adjacent
+/// elements. Note that it takes `O(n^2)` time in the worst case.
+pub fn sort_approx(values: &mut [Positive], eps: f64) {
+    for i in 1..values.len() {
+        let mut j = i;
+        while j > 0 && values[j - 1].cmp_approx(&values[j], eps) == Ordering::Greater {
+            values.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+/// Allows to compare [Positive] directly with a raw [f64] (e.g. a threshold) without wrapping it.
+///
+/// ```
+/// use rust_examples::typing::Positive;
+///
+/// let pos = Positive::new(42.).expect("positive number");
+/// assert!(pos == 42.);
+/// assert!(pos != f64::NAN);
+/// ```
+impl PartialEq<f64> for Positive {
+    #[inline]
+    fn eq(&self, other: &f64) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Ordering of [Positive] with respect to a raw [f64]. Because the right-hand side is not
+/// guaranteed to be comparable, a *nan* yields [None] (and all of `<`, `<=`, `>`, `>=` are
+/// `false`).
+///
+/// ```
+/// use rust_examples::typing::Positive;
+///
+/// let pos = Positive::new(42.).expect("positive number");
+/// assert!(pos > 1.);
+/// assert_eq!(pos.partial_cmp(&f64::NAN), None);
+/// ```
+impl PartialOrd<f64> for Positive {
+    #[inline]
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+        self.0.partial_cmp(other)
+    }
 }
 
 /// Safe and efficient version of comparison of two [Positive] floats.
@@ -152,9 +511,9 @@ impl Positive {
 /// [Positive] which makes the comparison both trivial and safe.
 ///
 /// # Pros
-/// 1. Now his operation is completely safe even though it uses unsafe. It is not possible to
-///    compile and run a program which calls this function with a negative number. This is a form of
-///    formal validation done by the compiler and thus much stronger result than any (unit) test!
+/// 1. Now his operation is completely safe. It is not possible to compile and run a program which
+///    calls this function with a negative number (or a *nan*). This is a form of formal validation
+///    done by the compiler and thus much stronger result than any (unit) test!
 /// 1. The constructor of the wrapper type pushes the clients to check for errors early on
 /// 1. The wrapper type carries certain semantics which can be taken to a benefit in the
 ///    implementation
@@ -163,11 +522,252 @@ impl Positive {
 /// 1. Clients must wrap their data into the wrapper type which might get tedious and not worth it
 ///    for non-critical data flows (although, this might be mitigated in the future).
 pub fn safe_cmp_f64(a: Positive, b: Positive) -> Ordering {
-    // Rust's `unsafe` keyword is basically saying: Hey, Borrow Checker, I'm now resposible for the semantics of the memory management
-    // in this block of code. Note that any crash in this scope results in an undefined behavior!
-    unsafe { a.as_u32().cmp(&b.as_u32()) }
+    // This is the same comparison of the raw bits as in `cmp_f64` and `better_cmp_f64`, except
+    // that the checks were already done once when the arguments were constructed
+    a.0.to_bits().cmp(&b.0.to_bits())
 }
 
+/// Total ordering of *all* [f64]s, including negative numbers and *nan*s.
+///
+/// This generalizes the idea behind [safe_cmp_f64] to the whole real line. The raw bits of a
+/// float are its sign bit followed by the magnitude (exponent and mantissa), so:
+///  1. bits of positive floats (sign bit `0`) interpreted as [u64] are already monotonic
+///  1. bits of negative floats (sign bit `1`) are monotonic in the *magnitude*, i.e. in the wrong
+///     direction, and all of them compare greater than any positive float
+///
+/// Both problems are fixed by a simple bit transformation. Flipping just the sign bit of positive
+/// floats moves them above all the negative ones, while flipping *all* the bits of negative floats
+/// clears their sign bit (moving them below the positive ones) and reverses their order. The
+/// transformed bits are then monotonic for all floats and can be compared as [u64]s.
+///
+/// The resulting order is the same as [f64::total_cmp] (i.e. the `totalOrder` predicate of the
+/// IEEE 754 standard):
+///  - negative *nan*s are ordered first and positive *nan*s last (note that [f64::NAN] is positive)
+///  - `-0.0` is ordered before `+0.0`
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use rust_examples::typing::total_cmp_f64;
+///
+/// assert_eq!(total_cmp_f64(-2., 1.), Ordering::Less);
+/// assert_eq!(total_cmp_f64(-2., -1.), Ordering::Less);
+/// assert_eq!(total_cmp_f64(f64::NAN, f64::INFINITY), Ordering::Greater);
+///
+/// let mut xs = vec![1., f64::NAN, -0., f64::NEG_INFINITY, 0., -4.2];
+/// xs.sort_by(|&a, &b| total_cmp_f64(a, b));
+/// assert_eq!(format!("{:?}", xs), "[-inf, -4.2, -0.0, 0.0, 1.0, NaN]");
+/// ```
+pub fn total_cmp_f64(a: f64, b: f64) -> Ordering {
+    const SIGN_MASK: u64 = 1 << 63;
+
+    fn key(x: f64) -> u64 {
+        let bits = x.to_bits();
+        if bits & SIGN_MASK == 0 {
+            // positive: move above all negatives
+            bits | SIGN_MASK
+        } else {
+            // negative: move below all positives and reverse the order
+            !bits
+        }
+    }
+
+    key(a).cmp(&key(b))
+}
+
+/// Counts how many `values` fall into each bucket defined by sorted `edges`.
+///
+/// The `n` edges define `n + 1` buckets, where the bucket `i` counts values `v` such that
+/// `edges[i - 1] <= v < edges[i]`. In particular:
+///  - values below the first edge are counted in the first bucket (index `0`)
+///  - values equal to or above the last edge are counted in the last bucket (index `n`)
+///
+/// The edges are searched by a binary search which relies on the total ordering of [Positive]s
+/// given by [safe_cmp_f64]. Note that unsorted `edges` produce meaningless counts.
+///
+/// ```
+/// use rust_examples::typing::{bucketize, Positive};
+///
+/// let pos = |x| Positive::new(x).expect("positive number");
+///
+/// let values = [pos(0.5), pos(1.), pos(1.5), pos(2.5), pos(10.)];
+/// let edges = [pos(1.), pos(2.)];
+///
+/// assert_eq!(bucketize(&values, &edges), vec![1, 2, 2]);
+/// ```
+pub fn bucketize(values: &[Positive], edges: &[Positive]) -> Vec<usize> {
+    debug_assert!(
+        edges
+            .windows(2)
+            .all(|e| safe_cmp_f64(e[0], e[1]) != Ordering::Greater),
+        "edges must be sorted"
+    );
+
+    let mut counts = vec![0; edges.len() + 1];
+    for &v in values {
+        let bucket = edges.partition_point(|&e| safe_cmp_f64(e, v) != Ordering::Greater);
+        counts[bucket] += 1;
+    }
+    counts
+}
+
+/// Computes the weighted average `sum(w * x) / sum(w)` of given `(x, w)` pairs.
+///
+/// Because the weights are [Positive], there's no need to check for negative weights (which
+/// would make the result meaningless). The only remaining error cases are an empty slice and zero
+/// total weight, in which case [None] is returned.
+///
+/// ```
+/// use rust_examples::typing::{weighted_mean, Positive};
+///
+/// let w = Positive::new(1.).expect("positive number");
+/// assert_eq!(weighted_mean(&[(1., w), (3., w)]), Some(2.));
+/// ```
+pub fn weighted_mean(values: &[(f64, Positive)]) -> Option<f64> {
+    let (weighted_sum, total_weight) = values.iter().fold((0., 0.), |(sum, total), &(x, w)| {
+        (sum + w.0 * x, total + w.0)
+    });
+
+    if total_weight > 0. {
+        Some(weighted_sum / total_weight)
+    } else {
+        None
+    }
+}
+
+/// Integer wrapper which generalizes the idea behind [Positive] to an arbitrary closed range
+/// `[MIN, MAX]` given by *constant generics*.
+///
+/// The range check is performed exactly once, in [Bounded::new], and afterwards the invariant
+/// `MIN <= v <= MAX` is carried by the type. Note that the bounds are part of the type, so for
+/// instance `Bounded<0, 10>` and `Bounded<0, 100>` are two distinct types.
+///
+/// # Zero-cost abstraction
+/// ```
+/// use std::mem::size_of;
+/// use rust_examples::typing::Bounded;
+///
+/// assert_eq!(size_of::<Bounded<0, 10>>(), size_of::<i64>());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<const MIN: i64, const MAX: i64>(i64);
+
+impl<const MIN: i64, const MAX: i64> Bounded<MIN, MAX> {
+    /// Constructs new [Bounded] only if `MIN <= v <= MAX`.
+    ///
+    /// ```
+    /// use rust_examples::typing::Bounded;
+    ///
+    /// assert_eq!(Bounded::<0, 10>::new(11), None);
+    /// ```
+    #[inline]
+    pub fn new(v: i64) -> Option<Self> {
+        if (MIN..=MAX).contains(&v) {
+            Some(Self(v))
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves the inner [i64] value
+    #[inline]
+    pub fn get(&self) -> i64 {
+        self.0
+    }
+}
+
+/// Opaque wrapper around [f64] which, contrary to [Positive], encodes the invariant that the float
+/// is [normal](f64::is_normal), i.e. neither a zero (`0.0` nor `-0.0`), a subnormal, an infinity
+/// nor a *nan*.
+///
+/// Such a value can safely be used as a divisor since division by it never divides by zero.
+/// Moreover, excluding subnormals and infinities guarantees that its reciprocal is finite and
+/// non-zero.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct NonZeroF64(f64);
+
+impl NonZeroF64 {
+    /// Constructs new [NonZeroF64] only if `number` is a normal float (see [f64::is_normal]).
+    ///
+    /// ```
+    /// use rust_examples::typing::NonZeroF64;
+    ///
+    /// assert_eq!(NonZeroF64::new(0.), None);
+    /// assert_eq!(NonZeroF64::new(-0.), None);
+    /// assert_eq!(NonZeroF64::new(f64::NAN), None);
+    /// assert_eq!(NonZeroF64::new(f64::INFINITY), None);
+    /// assert_eq!(NonZeroF64::new(f64::MIN_POSITIVE / 4.), None);
+    /// ```
+    #[inline]
+    pub fn new(number: f64) -> Option<Self> {
+        if !number.is_normal() {
+            None
+        } else {
+            Some(Self(number))
+        }
+    }
+
+    /// Retrieves the inner [f64] value
+    #[inline]
+    pub fn get(&self) -> f64 {
+        self.0
+    }
+
+    /// Computes the reciprocal `1 / x` which, by the construction of [NonZeroF64], is never a
+    /// result of a division by zero and is always finite and non-zero (and thus never a *nan*).
+    #[inline]
+    pub fn recip(&self) -> f64 {
+        self.0.recip()
+    }
+}
+
+/// Generalization of [Positive] to any floating point type `F` (e.g. [f32] or [f64]).
+///
+/// The invariant is the same as for [Positive] - the number is sign positive and not a *nan*.
+///
+/// ```
+/// use rust_examples::typing::PositiveNum;
+///
+/// assert_eq!(PositiveNum::new(4.2f32).map(|x| x.get()), Some(4.2f32));
+/// assert_eq!(PositiveNum::new(-4.2f64), None);
+/// ```
+#[cfg(feature = "num-traits")]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct PositiveNum<F: num_traits::Float>(F);
+
+#[cfg(feature = "num-traits")]
+impl<F: num_traits::Float> PositiveNum<F> {
+    /// Constructs new [PositiveNum] only if `number` is sign positive and not a *nan*.
+    #[inline]
+    pub fn new(number: F) -> Option<Self> {
+        if number.is_sign_positive() && !number.is_nan() {
+            Some(Self(number))
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves the inner float value
+    #[inline]
+    pub fn get(&self) -> F {
+        self.0
+    }
+}
+
+/// This test guards the invariant of [Positive] against a careless `#[derive(Neg)]` or
+/// `impl Neg for Positive` with `Output = Positive`. Negation of a positive float is not positive,
+/// so `-pos` must never produce a [Positive].
+///
+/// # Example
+/// ```compile_fail
+/// use rust_examples::typing::Positive;
+///
+/// let pos = Positive::new(42.).expect("positive number");
+/// let neg: Positive = -pos;
+/// ```
+///
+/// The correct way is to use [Positive::neg] which returns a [Signed] float.
+pub struct NegatePositiveTest;
+
 /// Structure that defines single field which has the type of the
 /// [*top type*](https://en.wikipedia.org/wiki/Top_type) in Rust.
 ///
@@ -245,19 +845,358 @@ pub struct UnitTypeExample {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use quickcheck::TestResult;
     use rstest::*;
 
+    #[cfg(feature = "num-traits")]
+    #[rstest]
+    #[case::positive(4.2, true)]
+    #[case::zero(0., true)]
+    #[case::infinity(f64::INFINITY, true)]
+    #[case::negative(-4.2, false)]
+    #[case::negative_zero(-0., false)]
+    #[case::nan(f64::NAN, false)]
+    fn positive_num(#[case] number: f64, #[case] valid: bool) {
+        let x = PositiveNum::new(number);
+        assert_eq!(x.is_some(), valid);
+        assert_eq!(
+            x.map(|x| x.get().to_bits()),
+            valid.then(|| number.to_bits())
+        );
+
+        let number = number as f32;
+        let x = PositiveNum::new(number);
+        assert_eq!(x.is_some(), valid);
+        assert_eq!(
+            x.map(|x| x.get().to_bits()),
+            valid.then(|| number.to_bits())
+        );
+    }
+
+    #[rstest]
+    #[case::empty_values(&[], &[1., 2.], vec![0, 0, 0])]
+    #[case::no_edges(&[1., 2.], &[], vec![2])]
+    #[case::inner(&[1.5, 1.2, 2.5], &[1., 2., 3.], vec![0, 2, 1, 0])]
+    #[case::below_first(&[0., 0.5], &[1., 2.], vec![2, 0, 0])]
+    #[case::above_last(&[2., 3., f64::INFINITY], &[1., 2.], vec![0, 0, 3])]
+    #[case::on_edges(&[1., 2., 3.], &[1., 2., 3.], vec![0, 1, 1, 1])]
+    fn bucket_counts(#[case] values: &[f64], #[case] edges: &[f64], #[case] expected: Vec<usize>) {
+        let pos = |&x: &f64| Positive::new(x).expect("positive number");
+        let values = values.iter().map(pos).collect::<Vec<_>>();
+        let edges = edges.iter().map(pos).collect::<Vec<_>>();
+        assert_eq!(bucketize(&values, &edges), expected);
+    }
+
+    #[rstest]
+    #[case::equal(1., 1., Ordering::Equal)]
+    #[case::within_eps(1., 1.0005, Ordering::Equal)]
+    #[case::within_eps_rev(1.0005, 1., Ordering::Equal)]
+    #[case::less(1., 1.1, Ordering::Less)]
+    #[case::greater(1.1, 1., Ordering::Greater)]
+    fn approx_ordering(#[case] x: f64, #[case] y: f64, #[case] expected: Ordering) {
+        let x = Positive::new(x).expect("positive number");
+        let y = Positive::new(y).expect("positive number");
+        assert_eq!(x.cmp_approx(&y, 1e-3), expected);
+    }
+
+    #[rstest]
+    fn approx_sorting() {
+        let pos = |&x: &f64| Positive::new(x).expect("positive number");
+
+        let mut values = [2.0, 1.0001, 3., 1., 1.9999, 0.5, 1.0002]
+            .iter()
+            .map(pos)
+            .collect::<Vec<_>>();
+
+        sort_approx(&mut values, 1e-3);
+
+        // Near-equal values are grouped together and keep their original relative order
+        let expected = [0.5, 1.0001, 1., 1.0002, 2.0, 1.9999, 3.]
+            .iter()
+            .map(pos)
+            .collect::<Vec<_>>();
+        assert_eq!(values, expected);
+    }
+
+    #[rstest]
+    #[case::one(Positive::ONE, 1.)]
+    #[case::e(Positive::E, std::f64::consts::E)]
+    #[case::pi(Positive::PI, std::f64::consts::PI)]
+    fn positive_consts(#[case] constant: Positive, #[case] expected: f64) {
+        assert_eq!(constant.get(), expected);
+        assert_eq!(Positive::new(constant.get()), Some(constant));
+    }
+
+    #[cfg(debug_assertions)]
+    #[rstest]
+    #[should_panic]
+    fn new_unchecked_asserts_in_debug() {
+        let _ = unsafe { Positive::new_unchecked(-1.) };
+    }
+
+    #[rstest]
+    #[case::normal(3., 1., 2.)]
+    #[case::fraction(1.5, 0.25, 1.25)]
+    #[case::equal(2., 2., f64::MIN_POSITIVE)]
+    #[case::negative(1., 3., f64::MIN_POSITIVE)]
+    #[case::infinities(f64::INFINITY, f64::INFINITY, f64::MIN_POSITIVE)]
+    fn saturating_sub(#[case] x: f64, #[case] y: f64, #[case] expected: f64) {
+        let x = Positive::new(x).expect("positive number");
+        let y = Positive::new(y).expect("positive number");
+        assert_eq!(x.saturating_sub(y), expected);
+    }
+
+    #[rstest]
+    fn stable_mean_compensates_summation_error() {
+        // Small values added to a large running total are lost by the naive summation
+        let n = 10_000;
+        let values = std::iter::once(1.)
+            .chain(std::iter::repeat_n(1e-16, n))
+            .map(|x| Positive::new(x).expect("positive number"))
+            .collect::<Vec<_>>();
+
+        let expected = (1. + n as f64 * 1e-16) / (n + 1) as f64;
+        let naive = values.iter().map(|x| x.get()).sum::<f64>() / values.len() as f64;
+        let stable = Positive::stable_mean(&values).expect("non-empty").get();
+
+        let naive_err = (naive - expected).abs() / expected;
+        let stable_err = (stable - expected).abs() / expected;
+
+        assert!(naive_err > 1e-13, "naive mean is exact: {}", naive);
+        assert!(stable_err < 1e-15, "stable mean {} != {}", stable, expected);
+    }
+
+    #[rstest]
+    #[case::empty(vec![], None)]
+    #[case::single(vec![4.2], Some(4.2))]
+    #[case::many(vec![1., 2., 3., 6.], Some(3.))]
+    #[case::overflow(vec![f64::MAX, f64::MAX], Some(f64::INFINITY))]
+    fn stable_mean(#[case] values: Vec<f64>, #[case] expected: Option<f64>) {
+        let values = values
+            .into_iter()
+            .map(|x| Positive::new(x).expect("positive number"))
+            .collect::<Vec<_>>();
+        assert_eq!(Positive::stable_mean(&values).map(|m| m.get()), expected);
+    }
+
+    #[rstest]
+    #[case::all_positive(&[1., 0., 4.2, f64::INFINITY], true)]
+    #[case::empty(&[], true)]
+    #[case::negative(&[1., -1., 4.2], false)]
+    #[case::nan(&[f64::NAN], false)]
+    fn positive_from_slice(#[case] xs: &[f64], #[case] all_positive: bool) {
+        let values = Positive::try_from_slice(xs);
+        assert_eq!(values.is_some(), all_positive);
+        if let Some(values) = values {
+            assert_eq!(values.iter().map(Positive::get).collect::<Vec<_>>(), xs);
+        }
+    }
+
+    #[rstest]
+    #[case::zero(0., Ok(0))]
+    #[case::integer(42., Ok(42))]
+    #[case::max(u32::MAX as f64, Ok(u32::MAX))]
+    #[case::fraction(4.2, Err(NotU32(4.2)))]
+    #[case::out_of_range(u32::MAX as f64 + 1., Err(NotU32(u32::MAX as f64 + 1.)))]
+    #[case::infinity(f64::INFINITY, Err(NotU32(f64::INFINITY)))]
+    fn positive_to_u32(#[case] number: f64, #[case] expected: Result<u32, NotU32>) {
+        let pos = Positive::new(number).expect("positive number");
+        assert_eq!(u32::try_from(pos), expected);
+    }
+
     #[rstest]
     #[case::greater(2., 1., Ordering::Greater)]
     #[case::less(1., 2., Ordering::Less)]
     #[case::equal(1., 1., Ordering::Equal)]
+    #[case::fraction(1.5, 1.2, Ordering::Greater)]
+    #[case::large(1e300, 4.2e10, Ordering::Greater)]
+    #[case::subnormal(f64::MIN_POSITIVE / 4., f64::MIN_POSITIVE / 2., Ordering::Less)]
     #[should_panic]
     #[case::error(2., -1., Ordering::Greater)]
     #[should_panic]
     #[case::error(-1., 2., Ordering::Less)]
     fn safe_float_cmp(#[case] a: f64, #[case] b: f64, #[case] expected: Ordering) {
-        let a = Positive::new(a).expect(&format!("a shold be a positive float, got {}", a));
-        let b = Positive::new(b).expect(&format!("b shold be a positive float, got {}", b));
+        let a =
+            Positive::new(a).unwrap_or_else(|| panic!("a shold be a positive float, got {}", a));
+        let b =
+            Positive::new(b).unwrap_or_else(|| panic!("b shold be a positive float, got {}", b));
         assert_eq!(safe_cmp_f64(a, b), expected);
     }
+
+    /// Generating floats from raw bits (with the sign bit cleared) covers the whole range of
+    /// positive floats including subnormals, large magnitudes and infinity.
+    #[quickcheck]
+    fn safe_cmp_matches_f64_ordering(a: u64, b: u64) -> TestResult {
+        const SIGN_MASK: u64 = 1 << 63;
+
+        let (a, b) = (
+            f64::from_bits(a & !SIGN_MASK),
+            f64::from_bits(b & !SIGN_MASK),
+        );
+
+        match (Positive::new(a), Positive::new(b)) {
+            (Some(pa), Some(pb)) => {
+                TestResult::from_bool(safe_cmp_f64(pa, pb) == a.partial_cmp(&b).unwrap())
+            }
+            // Discard nan inputs
+            _ => TestResult::discard(),
+        }
+    }
+
+    #[rstest]
+    #[case::greater(2., 1.)]
+    #[case::less(1., 2.)]
+    #[case::equal(1., 1.)]
+    #[case::fraction(1.5, 1.2)]
+    #[case::large(1e300, 4.2e10)]
+    #[case::infinity(f64::INFINITY, f64::MAX)]
+    #[case::subnormal(f64::MIN_POSITIVE / 4., f64::MIN_POSITIVE / 2.)]
+    fn float_cmp_implementations_agree(#[case] a: f64, #[case] b: f64) {
+        let expected = a.partial_cmp(&b);
+        let pa = Positive::new(a).expect("positive number");
+        let pb = Positive::new(b).expect("positive number");
+
+        assert_eq!(Some(cmp_f64(a, b)), expected);
+        assert_eq!(better_cmp_f64(a, b), expected);
+        assert_eq!(Some(safe_cmp_f64(pa, pb)), expected);
+    }
+
+    #[rstest]
+    #[case::negative(-1., 2.)]
+    #[case::nan(f64::NAN, 2.)]
+    fn float_cmp_rejects(#[case] a: f64, #[case] b: f64) {
+        assert_eq!(better_cmp_f64(a, b), None);
+        assert_eq!(better_cmp_f64(b, a), None);
+        assert!(std::panic::catch_unwind(|| cmp_f64(a, b)).is_err());
+    }
+
+    #[rstest]
+    #[case::negative_positive(-1., 1., Ordering::Less)]
+    #[case::positive_negative(4.2, -42., Ordering::Greater)]
+    #[case::two_negatives(-2., -1., Ordering::Less)]
+    #[case::two_negatives_reversed(-1., -2., Ordering::Greater)]
+    #[case::equal_negatives(-4.2, -4.2, Ordering::Equal)]
+    #[case::zeros(-0., 0., Ordering::Less)]
+    #[case::infinities(f64::NEG_INFINITY, f64::INFINITY, Ordering::Less)]
+    #[case::nan_last(f64::NAN, f64::INFINITY, Ordering::Greater)]
+    #[case::nan_equal(f64::NAN, f64::NAN, Ordering::Equal)]
+    #[case::negative_nan_first(-f64::NAN, f64::NEG_INFINITY, Ordering::Less)]
+    fn total_cmp(#[case] a: f64, #[case] b: f64, #[case] expected: Ordering) {
+        assert_eq!(total_cmp_f64(a, b), expected);
+        assert_eq!(total_cmp_f64(b, a), expected.reverse());
+    }
+
+    /// Arbitrary bits cover all the floats, including both *nan*s, zeros and infinities.
+    #[quickcheck]
+    fn total_cmp_matches_std(a: u64, b: u64) -> bool {
+        let (a, b) = (f64::from_bits(a), f64::from_bits(b));
+        total_cmp_f64(a, b) == a.total_cmp(&b)
+    }
+
+    #[rstest]
+    #[case::equal(1., Some(Ordering::Equal))]
+    #[case::greater(0.5, Some(Ordering::Greater))]
+    #[case::less(2., Some(Ordering::Less))]
+    #[case::nan(f64::NAN, None)]
+    fn cmp_with_raw_f64(#[case] other: f64, #[case] expected: Option<Ordering>) {
+        let pos = Positive::new(1.).expect("positive number");
+        assert_eq!(pos.partial_cmp(&other), expected);
+        assert_eq!(pos == other, expected == Some(Ordering::Equal));
+        assert_eq!(pos > other, expected == Some(Ordering::Greater));
+        assert_eq!(pos < other, expected == Some(Ordering::Less));
+    }
+
+    #[rstest]
+    #[case::zero(0.)]
+    #[case::one(1.)]
+    #[case::large(4.2e42)]
+    fn positive_round_trip(#[case] number: f64) {
+        let pos = Positive::try_from(number).expect("positive number");
+        assert_eq!(pos.get(), number);
+        assert_eq!(f64::from(pos), number);
+    }
+
+    #[rstest]
+    #[case::neg(-1.)]
+    #[case::neg_zero(-0.)]
+    #[case::neg_inf(f64::NEG_INFINITY)]
+    fn not_positive(#[case] number: f64) {
+        assert_eq!(Positive::try_from(number), Err(NotPositive(number)));
+    }
+
+    #[rstest]
+    #[case::min(0, Some(0))]
+    #[case::max(10, Some(10))]
+    #[case::inside(4, Some(4))]
+    #[case::below(-1, None)]
+    #[case::above(11, None)]
+    fn bounded_range(#[case] v: i64, #[case] expected: Option<i64>) {
+        assert_eq!(Bounded::<0, 10>::new(v).map(|b| b.get()), expected);
+    }
+
+    #[rstest]
+    #[case::zero(0.)]
+    #[case::neg_zero(-0.)]
+    #[case::nan(f64::NAN)]
+    #[case::inf(f64::INFINITY)]
+    #[case::neg_inf(f64::NEG_INFINITY)]
+    #[case::subnormal(f64::MIN_POSITIVE / 4.)]
+    #[case::neg_subnormal(-f64::MIN_POSITIVE / 4.)]
+    fn non_zero_rejects(#[case] number: f64) {
+        assert_eq!(NonZeroF64::new(number), None);
+    }
+
+    #[rstest]
+    #[case::pos(2., 0.5)]
+    #[case::neg(-4., -0.25)]
+    #[case::min_normal(f64::MIN_POSITIVE, 1. / f64::MIN_POSITIVE)]
+    #[case::max(f64::MAX, 1. / f64::MAX)]
+    fn non_zero_recip(#[case] number: f64, #[case] expected: f64) {
+        let x = NonZeroF64::new(number).expect("non-zero number");
+        assert_eq!(x.get(), number);
+        assert_eq!(x.recip(), expected);
+        assert!(x.recip().is_finite() && x.recip() != 0.);
+    }
+
+    #[rstest]
+    fn min_heap_of_positives() {
+        use std::cmp::Reverse;
+        use std::collections::BinaryHeap;
+
+        let mut heap = [4.2, 0.5, 42., 1., 0.]
+            .iter()
+            .map(|&x| Positive::new(x).map(Reverse).expect("positive number"))
+            .collect::<BinaryHeap<_>>();
+
+        let mut ascending = Vec::new();
+        while let Some(Reverse(pos)) = heap.pop() {
+            ascending.push(pos.get());
+        }
+
+        assert_eq!(ascending, vec![0., 0.5, 1., 4.2, 42.]);
+    }
+
+    #[rstest]
+    #[case::known(vec![(1., 1.), (2., 3.), (10., 0.)], Some(1.75))]
+    #[case::single(vec![(4.2, 0.5)], Some(4.2))]
+    #[case::empty(vec![], None)]
+    #[case::zero_weight(vec![(1., 0.), (2., 0.)], None)]
+    fn weighted_average(#[case] values: Vec<(f64, f64)>, #[case] expected: Option<f64>) {
+        let values = values
+            .into_iter()
+            .map(|(x, w)| (x, Positive::new(w).expect("positive weight")))
+            .collect::<Vec<_>>();
+        assert_eq!(weighted_mean(&values), expected);
+    }
+
+    #[rstest]
+    #[case::one(1.)]
+    #[case::large(4.2e42)]
+    #[case::inf(f64::INFINITY)]
+    fn negate_positive(#[case] number: f64) {
+        let pos = Positive::new(number).expect("positive number");
+        let neg = pos.neg();
+        assert!(neg.get() < 0.);
+        assert_eq!(neg.get(), -number);
+    }
 }